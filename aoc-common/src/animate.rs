@@ -0,0 +1,45 @@
+//! Simulations that can be watched step by step, drawn as text frames.
+
+use crate::bbox::BBox2;
+use crate::picture::Picture;
+use crate::point::{Coord, Point2};
+
+/// A simulation advanced one step at a time, which can draw its current state.
+pub trait Animation {
+    /// The current state, as lines of text.
+    fn frame(&self) -> String;
+
+    /// Advances one step. Returns `false`, without changing anything, once the simulation is
+    /// over.
+    fn step(&mut self) -> bool;
+
+    /// The current state as a picture, for the simulations that have colours for it.
+    fn picture(&self) -> Option<Picture> { None }
+}
+
+/// Draws the points of `bounds` row by row, with `f` choosing the character of each.
+pub fn draw<T: Coord, F>(bounds: &BBox2<T>, mut f: F) -> String
+where
+    F: FnMut(Point2<T>) -> char,
+{
+    let mut out = String::new();
+    for p in bounds.points() {
+        out.push(f(p));
+        if p.x == bounds.max.x {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draw() {
+        let bounds = BBox2 { min: Point2 { x: -1, y: 0 }, max: Point2 { x: 1, y: 1 } };
+        let frame = draw(&bounds, |p| if p.x == p.y { '#' } else { '.' });
+        assert_eq!(frame, ".#.\n..#\n");
+    }
+}
@@ -0,0 +1,36 @@
+use crate::verbose::span;
+
+/// The answers to both parts of a puzzle.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Answers<A, B = A> {
+    pub part1: A,
+    pub part2: B,
+}
+
+impl<A, B> Answers<A, B> {
+    /// Runs both parts on the same parsed input, each in a span (see `verbose`).
+    pub fn solve<I, F, G>(input: &I, part1: F, part2: G) -> Answers<A, B>
+    where
+        I: ?Sized,
+        F: FnOnce(&I) -> A,
+        G: FnOnce(&I) -> B,
+    {
+        let part1 = {
+            let _span = span("part 1");
+            part1(input)
+        };
+        let _span = span("part 2");
+        Answers { part1, part2: part2(input) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let answers = Answers::solve("a,bb,ccc", |s| s.len(), |s| s.split(',').count());
+        assert_eq!(answers, Answers { part1: 8, part2: 3 });
+    }
+}
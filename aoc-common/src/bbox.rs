@@ -0,0 +1,130 @@
+use crate::point::{Coord, Point2, Point3};
+
+/// An axis-aligned 2D box, inclusive on both ends.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BBox2<T> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+impl<T: Coord> BBox2<T> {
+    /// The smallest box containing all points, or `None` if there are none.
+    pub fn from_points<'a, I>(points: I) -> Option<BBox2<T>>
+    where
+        I: IntoIterator<Item = &'a Point2<T>>,
+        T: 'a,
+    {
+        let mut points = points.into_iter();
+        let first = *points.next()?;
+        Some(points.fold(BBox2 { min: first, max: first }, |b, p| b.including(p)))
+    }
+
+    /// The smallest box containing both this box and `p`.
+    pub fn including(&self, p: &Point2<T>) -> BBox2<T> {
+        BBox2 {
+            min: Point2 { x: self.min.x.min(p.x), y: self.min.y.min(p.y) },
+            max: Point2 { x: self.max.x.max(p.x), y: self.max.y.max(p.y) },
+        }
+    }
+
+    /// Grows the box by `n` in every direction.
+    pub fn expand(&self, n: T) -> BBox2<T> {
+        let d = Point2 { x: n, y: n };
+        BBox2 { min: self.min - d, max: self.max + d }
+    }
+
+    pub fn contains(&self, p: &Point2<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    pub fn width(&self) -> T { self.max.x - self.min.x + T::ONE }
+
+    pub fn height(&self) -> T { self.max.y - self.min.y + T::ONE }
+
+    pub fn area(&self) -> T { self.width() * self.height() }
+
+    /// All points in the box, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point2<T>> {
+        let b = *self;
+        let mut next = Some(b.min);
+        std::iter::from_fn(move || {
+            let p = next?;
+            next = if p.x < b.max.x {
+                Some(Point2 { x: p.x + T::ONE, ..p })
+            } else if p.y < b.max.y {
+                Some(Point2 { x: b.min.x, y: p.y + T::ONE })
+            } else {
+                None
+            };
+            Some(p)
+        })
+    }
+}
+
+/// An axis-aligned 3D box, inclusive on both ends.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BBox3<T> {
+    pub min: Point3<T>,
+    pub max: Point3<T>,
+}
+
+impl<T: Coord> BBox3<T> {
+    /// The smallest box containing all points, or `None` if there are none.
+    pub fn from_points<'a, I>(points: I) -> Option<BBox3<T>>
+    where
+        I: IntoIterator<Item = &'a Point3<T>>,
+        T: 'a,
+    {
+        let mut points = points.into_iter();
+        let first = *points.next()?;
+        Some(points.fold(BBox3 { min: first, max: first }, |b, p| b.including(p)))
+    }
+
+    /// The smallest box containing both this box and `p`.
+    pub fn including(&self, p: &Point3<T>) -> BBox3<T> {
+        BBox3 {
+            min: Point3 { x: self.min.x.min(p.x), y: self.min.y.min(p.y), z: self.min.z.min(p.z) },
+            max: Point3 { x: self.max.x.max(p.x), y: self.max.y.max(p.y), z: self.max.z.max(p.z) },
+        }
+    }
+
+    /// Grows the box by `n` in every direction.
+    pub fn expand(&self, n: T) -> BBox3<T> {
+        let d = Point3 { x: n, y: n, z: n };
+        BBox3 { min: self.min - d, max: self.max + d }
+    }
+
+    pub fn contains(&self, p: &Point3<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x)
+            && (self.min.y..=self.max.y).contains(&p.y)
+            && (self.min.z..=self.max.z).contains(&p.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bbox2() {
+        let points = [Point2 { x: 2, y: -1 }, Point2 { x: -3, y: 4 }, Point2 { x: 0, y: 0 }];
+        let b = BBox2::from_points(&points).unwrap();
+        assert_eq!(b.min, Point2 { x: -3, y: -1 });
+        assert_eq!(b.max, Point2 { x: 2, y: 4 });
+        assert_eq!(b.area(), 36);
+        assert_eq!(b.points().count(), 36);
+        assert!(b.points().all(|p| b.contains(&p)));
+        assert!(!b.contains(&Point2 { x: 3, y: 0 }));
+        assert!(b.expand(1).contains(&Point2 { x: 3, y: 0 }));
+        assert_eq!(BBox2::<i32>::from_points(&[]), None);
+    }
+
+    #[test]
+    fn test_bbox3() {
+        let points = [Point3 { x: 1, y: 2, z: 3 }, Point3 { x: 3, y: 2, z: 1 }];
+        let b = BBox3::from_points(&points).unwrap();
+        assert!(b.contains(&Point3 { x: 2, y: 2, z: 2 }));
+        assert!(!b.contains(&Point3 { x: 2, y: 3, z: 2 }));
+        assert!(b.expand(1).contains(&Point3 { x: 2, y: 3, z: 2 }));
+    }
+}
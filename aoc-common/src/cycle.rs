@@ -0,0 +1,120 @@
+//! Finding where a sequence of states starts repeating, to skip ahead to far away steps.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A sequence that, after the first `start` steps, repeats every `period` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The step in the first round of the cycle equivalent to step `n`.
+    pub fn index(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+
+    /// The value at step `n` of a quantity that grows by the same amount every period, like
+    /// the height of a tower, from its `values` at the first `start + period + 1` steps.
+    pub fn extrapolate(&self, values: &[i64], n: usize) -> i64 {
+        if n < self.start {
+            return values[n];
+        }
+        let growth = values[self.start + self.period] - values[self.start];
+        let periods = ((n - self.start) / self.period) as i64;
+        values[self.index(n)] + periods * growth
+    }
+}
+
+/// The cycle in `states`, found when a state comes back, by remembering every state seen. States
+/// can be anything determining what comes next, e.g. fingerprints of a simulation. `None` if
+/// `states` ends without repeating.
+pub fn find_cycle<S, I>(states: I) -> Option<Cycle>
+where
+    S: Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut seen = HashMap::new();
+    for (i, state) in states.into_iter().enumerate() {
+        if let Some(start) = seen.insert(state, i) {
+            return Some(Cycle { start, period: i - start });
+        }
+    }
+    None
+}
+
+/// The cycle of repeatedly applying `f` to `first`, with Brent's algorithm, which only keeps
+/// two states around. Never returns if the states don't repeat.
+pub fn brent<S, F>(first: S, mut f: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    // Find the period, moving the tortoise to the hare at every power of two.
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = first.clone();
+    let mut hare = f(&first);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = f(&hare);
+        period += 1;
+    }
+
+    // With the hare a period ahead, they meet where the cycle starts.
+    let mut tortoise = first.clone();
+    let mut hare = first;
+    for _ in 0..period {
+        hare = f(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+    Cycle { start, period }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(x: &u64) -> u64 { (x * x + 1) % 255 }
+
+    #[test]
+    fn test_find_cycle() {
+        assert_eq!(find_cycle([1, 2, 3, 4, 5, 3, 4, 5, 3]), Some(Cycle { start: 2, period: 3 }));
+        assert_eq!(find_cycle([1, 2, 3]), None);
+    }
+
+    #[test]
+    fn test_brent_matches_hashing() {
+        for first in 0..50 {
+            let states = std::iter::successors(Some(first), |x| Some(step(x)));
+            assert_eq!(Some(brent(first, step)), find_cycle(states));
+        }
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        // 0, 1, then 2 more every 3 steps: 3, 4, 5 | 5, 6, 7 | 7, ...
+        let cycle = Cycle { start: 2, period: 3 };
+        let values = [0, 1, 3, 4, 5, 5];
+        assert_eq!(cycle.extrapolate(&values, 1), 1);
+        assert_eq!(cycle.extrapolate(&values, 5), 5);
+        assert_eq!(cycle.extrapolate(&values, 9), 8);
+        assert_eq!(cycle.extrapolate(&values, 2 + 3 * 1000), 2003);
+        assert_eq!(cycle.index(9), 3);
+    }
+}
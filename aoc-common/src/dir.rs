@@ -0,0 +1,119 @@
+use crate::point::{Coord, Point2};
+
+/// The four orthogonal directions, with north pointing up (towards negative y).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Dir4 {
+    N,
+    E,
+    S,
+    W,
+}
+
+impl Dir4 {
+    /// All directions in clockwise order, starting north.
+    pub const ALL: [Dir4; 4] = [Dir4::N, Dir4::E, Dir4::S, Dir4::W];
+
+    pub fn delta<T: Coord>(self) -> Point2<T> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        match self {
+            Dir4::N => Point2 { x: zero, y: -one },
+            Dir4::E => Point2 { x: one, y: zero },
+            Dir4::S => Point2 { x: zero, y: one },
+            Dir4::W => Point2 { x: -one, y: zero },
+        }
+    }
+
+    pub fn turn_left(self) -> Dir4 { Dir4::ALL[(self as usize + 3) % 4] }
+
+    pub fn turn_right(self) -> Dir4 { Dir4::ALL[(self as usize + 1) % 4] }
+
+    pub fn opposite(self) -> Dir4 { Dir4::ALL[(self as usize + 2) % 4] }
+
+    /// Parses the arrows `^>v<` used to draw directions on maps.
+    pub fn from_arrow(c: char) -> Option<Dir4> {
+        match c {
+            '^' => Some(Dir4::N),
+            '>' => Some(Dir4::E),
+            'v' => Some(Dir4::S),
+            '<' => Some(Dir4::W),
+            _ => None,
+        }
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Dir4::N => '^',
+            Dir4::E => '>',
+            Dir4::S => 'v',
+            Dir4::W => '<',
+        }
+    }
+}
+
+/// The eight compass directions, with north pointing up (towards negative y).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    /// All directions in clockwise order, starting north.
+    pub const ALL: [Dir8; 8] =
+        [Dir8::N, Dir8::NE, Dir8::E, Dir8::SE, Dir8::S, Dir8::SW, Dir8::W, Dir8::NW];
+
+    pub fn delta<T: Coord>(self) -> Point2<T> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        match self {
+            Dir8::N => Point2 { x: zero, y: -one },
+            Dir8::NE => Point2 { x: one, y: -one },
+            Dir8::E => Point2 { x: one, y: zero },
+            Dir8::SE => Point2 { x: one, y: one },
+            Dir8::S => Point2 { x: zero, y: one },
+            Dir8::SW => Point2 { x: -one, y: one },
+            Dir8::W => Point2 { x: -one, y: zero },
+            Dir8::NW => Point2 { x: -one, y: -one },
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(d: Dir4) -> Dir8 {
+        match d {
+            Dir4::N => Dir8::N,
+            Dir4::E => Dir8::E,
+            Dir4::S => Dir8::S,
+            Dir4::W => Dir8::W,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turn() {
+        assert_eq!(Dir4::N.turn_left(), Dir4::W);
+        assert_eq!(Dir4::N.turn_right(), Dir4::E);
+        assert_eq!(Dir4::W.turn_right(), Dir4::N);
+        assert_eq!(Dir4::E.opposite(), Dir4::W);
+        for d in Dir4::ALL {
+            assert_eq!(d.delta::<i32>().rotate_left(), d.turn_left().delta());
+            assert_eq!(d.delta::<i32>().rotate_right(), d.turn_right().delta());
+        }
+    }
+
+    #[test]
+    fn test_dir8_matches_dir4() {
+        for d in Dir4::ALL {
+            assert_eq!(d.delta::<i32>(), Dir8::from(d).delta());
+        }
+    }
+}
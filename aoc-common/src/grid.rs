@@ -0,0 +1,190 @@
+use crate::parse::{Line, ParseError};
+use crate::point::{Coord, Point2};
+use std::ops::{Index, IndexMut};
+
+/// A dense, rectangular 2D grid stored row by row. Positions are `Point2`s with `x` as the column
+/// and `y` as the row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid { width, height, cells: vec![fill; width * height] }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |r| r.len());
+        assert!(rows.iter().all(|r| r.len() == width), "Grid rows differ in length");
+        Grid { width, height, cells: rows.into_iter().flatten().collect() }
+    }
+
+    /// Parses a character map, one row per line.
+    pub fn parse<F>(input: &str, mut f: F) -> Grid<T>
+    where
+        F: FnMut(char) -> T,
+    {
+        Grid::from_rows(input.lines().map(|line| line.chars().map(&mut f).collect()).collect())
+    }
+
+    /// Like `parse`, but `f` may reject characters, and rows that differ in length from the first
+    /// are an error rather than a panic.
+    pub fn try_parse<F>(
+        day: &'static str,
+        input: &str,
+        expected: &str,
+        mut f: F,
+    ) -> Result<Grid<T>, ParseError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut rows: Vec<Vec<T>> = Vec::new();
+        for line in Line::all(day, input) {
+            let row = line
+                .text
+                .chars()
+                .enumerate()
+                .map(|(i, c)| f(c).ok_or_else(|| line.error_at_char(i, expected)))
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some(first) = rows.first() {
+                if row.len() != first.len() {
+                    let expected = format!("a row of {} cells", first.len());
+                    return Err(line.error_at_char(row.len().min(first.len()), expected));
+                }
+            }
+            rows.push(row);
+        }
+        Ok(Grid::from_rows(rows))
+    }
+
+    pub fn width(&self) -> usize { self.width }
+
+    pub fn height(&self) -> usize { self.height }
+
+    fn index_of<C: Coord>(&self, p: Point2<C>) -> Option<usize> {
+        let (x, y) = (p.x.to_usize()?, p.y.to_usize()?);
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    fn pos_of<C: Coord>(&self, i: usize) -> Point2<C> {
+        Point2 { x: C::from_usize(i % self.width), y: C::from_usize(i / self.width) }
+    }
+
+    pub fn contains<C: Coord>(&self, p: Point2<C>) -> bool { self.index_of(p).is_some() }
+
+    pub fn get<C: Coord>(&self, p: Point2<C>) -> Option<&T> {
+        self.index_of(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut<C: Coord>(&mut self, p: Point2<C>) -> Option<&mut T> {
+        self.index_of(p).map(|i| &mut self.cells[i])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> { self.cells.chunks(self.width.max(1)) }
+
+    pub fn iter<C: Coord>(&self) -> impl Iterator<Item = (Point2<C>, &T)> {
+        self.cells.iter().enumerate().map(|(i, v)| (self.pos_of(i), v))
+    }
+
+    /// Position of the first cell (in reading order) matching `pred`.
+    pub fn find<C: Coord, F>(&self, pred: F) -> Option<Point2<C>>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.cells.iter().position(pred).map(|i| self.pos_of(i))
+    }
+
+    /// The orthogonal neighbours of `p` that lie within the grid.
+    pub fn neighbours4<C: Coord>(&self, p: Point2<C>) -> impl Iterator<Item = Point2<C>> + '_ {
+        p.neighbours4().filter(|n| self.contains(*n))
+    }
+
+    /// The orthogonal and diagonal neighbours of `p` that lie within the grid.
+    pub fn neighbours8<C: Coord>(&self, p: Point2<C>) -> impl Iterator<Item = Point2<C>> + '_ {
+        p.neighbours8().filter(|n| self.contains(*n))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+
+    /// Draws the grid with one character per cell.
+    pub fn render<F>(&self, f: F) -> String
+    where
+        F: Fn(&T) -> char,
+    {
+        self.rows().map(|row| row.iter().map(&f).chain(['\n']).collect::<String>()).collect()
+    }
+}
+
+impl<T, C: Coord> Index<Point2<C>> for Grid<T> {
+    type Output = T;
+    fn index(&self, p: Point2<C>) -> &T {
+        match self.get(p) {
+            Some(v) => v,
+            None => panic!("{:?} out of bounds for {}x{} grid", p, self.width, self.height),
+        }
+    }
+}
+
+impl<T, C: Coord> IndexMut<Point2<C>> for Grid<T> {
+    fn index_mut(&mut self, p: Point2<C>) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(p) {
+            Some(v) => v,
+            None => panic!("{:?} out of bounds for {}x{} grid", p, width, height),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse("ab\ncd\nef\n", |c| c);
+        assert_eq!(grid.width(), 2);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid[Point2 { x: 1, y: 2 }], 'f');
+        assert_eq!(grid.get(Point2 { x: 2, y: 0 }), None);
+        assert_eq!(grid.get(Point2 { x: -1, y: 0 }), None);
+        assert_eq!(grid.find(|c| *c == 'd'), Some(Point2 { x: 1, y: 1 }));
+        assert_eq!(grid.render(|c| c.to_ascii_uppercase()), "AB\nCD\nEF\n");
+    }
+
+    #[test]
+    fn test_try_parse() {
+        let digit = |c: char| c.to_digit(10);
+        let grid = Grid::try_parse("day0", "12\n34\n", "a digit", digit).unwrap();
+        assert_eq!(grid[Point2 { x: 0, y: 1 }], 3);
+
+        let err = Grid::try_parse("day0", "12\n3x\n", "a digit", digit).unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_deref()), (2, 2, Some("x")));
+        let err = Grid::try_parse("day0", "12\n345\n", "a digit", digit).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+
+    #[test]
+    fn test_neighbours_clipped() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(grid.neighbours4(Point2 { x: 0, y: 0 }).count(), 2);
+        assert_eq!(grid.neighbours4(Point2 { x: 1, y: 1 }).count(), 4);
+        assert_eq!(grid.neighbours8(Point2 { x: 0, y: 1 }).count(), 5);
+        assert_eq!(grid.neighbours8(Point2 { x: 1, y: 1 }).count(), 8);
+    }
+}
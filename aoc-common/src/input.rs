@@ -0,0 +1,159 @@
+//! Reads puzzle input from a file, stdin (`-`) or a file compressed with gzip (`.gz`) or zstd
+//! (`.zst`), telling them apart by the path alone.
+//!
+//! Whatever the source, lines come without their `\r\n` or `\n` endings, and blank lines at the
+//! end are dropped: `read` gives the remaining lines each ended by `\n`, which is also how the
+//! days' parsers expect them.
+
+use flate2::read::MultiGzDecoder;
+use ruzstd::decoding::StreamingDecoder;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+/// The path standing for stdin.
+pub const STDIN: &str = "-";
+
+/// The decompressed bytes of `path`, or of stdin for `-`.
+pub fn open(path: &str) -> io::Result<Box<dyn BufRead>> {
+    if path == STDIN {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    let file = BufReader::new(File::open(path).map_err(|e| with_path(path, e))?);
+    Ok(match Path::new(path).extension().and_then(|e| e.to_str()) {
+        Some("gz") => Box::new(BufReader::new(MultiGzDecoder::new(file))),
+        Some("zst") => {
+            let decoder = StreamingDecoder::new(file)
+                .map_err(|e| with_path(path, io::Error::new(io::ErrorKind::InvalidData, e)))?;
+            Box::new(BufReader::new(decoder))
+        }
+        _ => Box::new(file),
+    })
+}
+
+fn with_path(path: &str, e: io::Error) -> io::Error {
+    io::Error::new(e.kind(), format!("Can't read {path}: {e}"))
+}
+
+/// The lines of `path`, read as they are needed.
+pub fn lines(path: &str) -> io::Result<Lines<Box<dyn BufRead>>> { Ok(Lines::new(open(path)?)) }
+
+/// All of `path` as text: its lines, each ended by `\n`.
+pub fn read(path: &str) -> io::Result<String> {
+    normalize(open(path)?).map_err(|e| with_path(path, e))
+}
+
+/// The lines of `reader` as text, each ended by `\n`.
+pub fn normalize<R: BufRead>(reader: R) -> io::Result<String> {
+    let mut text = String::new();
+    for line in Lines::new(reader) {
+        text += &line?;
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+/// The lines of a reader without their endings, leaving out the blank lines at the end. Blank
+/// lines before others come out empty.
+pub struct Lines<R> {
+    reader: R,
+    /// Blank lines read ahead, only given out once a line that isn't follows them. Only counted,
+    /// so that any number of them takes no memory.
+    blank: usize,
+    /// The line that followed them.
+    next: Option<String>,
+}
+
+impl<R: BufRead> Lines<R> {
+    pub fn new(reader: R) -> Lines<R> { Lines { reader, blank: 0, next: None } }
+
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        if line.ends_with('\n') {
+            line.pop();
+            if line.ends_with('\r') {
+                line.pop();
+            }
+        }
+        Ok(Some(line))
+    }
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<io::Result<String>> {
+        while self.next.is_none() {
+            match self.read_line() {
+                Ok(Some(line)) if line.trim().is_empty() => self.blank += 1,
+                Ok(Some(line)) => self.next = Some(line),
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        if self.blank > 0 {
+            self.blank -= 1;
+            return Some(Ok(String::new()));
+        }
+        self.next.take().map(Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+
+    #[test]
+    fn test_normalize() {
+        let text = |s: &str| normalize(s.as_bytes()).unwrap();
+        assert_eq!(text("a\r\n\r\nb  \n \n\n"), "a\n\nb  \n");
+        assert_eq!(text("a\n  b"), "a\n  b\n");
+        assert_eq!(text("\n\n"), "");
+        let lines: Vec<String> =
+            Lines::new("\n1\n\n\n2\n\n".as_bytes()).map(Result::unwrap).collect();
+        assert_eq!(lines, ["", "1", "", "", "2"]);
+        // A million blank lines, then one of spaces, come out empty before the next line.
+        let blank = io::repeat(b'\n').take(1 << 20).chain(" \nx\n".as_bytes());
+        let lines: Vec<String> = Lines::new(BufReader::new(blank)).map(Result::unwrap).collect();
+        assert_eq!(lines.len(), (1 << 20) + 2);
+        assert!(lines[..=1 << 20].iter().all(String::is_empty));
+        assert_eq!(lines.last().unwrap(), "x");
+    }
+
+    #[test]
+    fn test_compressed() {
+        let dir = std::env::temp_dir();
+        let text = "1000\n2000\n\n3000\n";
+
+        let gz = dir.join(format!("aoc-input-{}.txt.gz", std::process::id()));
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        encoder.write_all(text.as_bytes()).unwrap();
+        std::fs::write(&gz, encoder.finish().unwrap()).unwrap();
+
+        let zst = dir.join(format!("aoc-input-{}.txt.zst", std::process::id()));
+        let level = ruzstd::encoding::CompressionLevel::Fastest;
+        std::fs::write(&zst, ruzstd::encoding::compress_to_vec(text.as_bytes(), level)).unwrap();
+
+        for path in [&gz, &zst] {
+            assert_eq!(read(path.to_str().unwrap()).unwrap(), text);
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let e = read("no/such/input.txt").unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::NotFound);
+        assert!(e.to_string().starts_with("Can't read no/such/input.txt: "));
+
+        let bad = std::env::temp_dir().join(format!("aoc-input-{}.gz", std::process::id()));
+        std::fs::write(&bad, "not gzip").unwrap();
+        let e = read(bad.to_str().unwrap()).unwrap_err();
+        std::fs::remove_file(&bad).unwrap();
+        assert!(e.to_string().starts_with("Can't read "));
+    }
+}
@@ -0,0 +1,24 @@
+//! Shared building blocks for the Advent of Code 2022 solutions.
+
+pub mod animate;
+pub mod answers;
+pub mod bbox;
+pub mod cycle;
+pub mod dir;
+pub mod grid;
+pub mod input;
+pub mod params;
+pub mod parse;
+pub mod picture;
+pub mod point;
+pub mod search;
+pub mod validate;
+pub mod verbose;
+
+pub use answers::Answers;
+pub use bbox::{BBox2, BBox3};
+pub use dir::{Dir4, Dir8};
+pub use grid::Grid;
+pub use parse::{Line, ParseError};
+pub use point::{Point2, Point3};
+pub use validate::Violation;
@@ -0,0 +1,230 @@
+//! Puzzle parameters of the days, like sizes and numbers of rounds, which can be set from the
+//! command line as `--name value` options, e.g. `--rounds 20`.
+
+use crate::input::STDIN;
+use crate::verbose::set_verbose;
+use std::env;
+use std::fmt::Display;
+use std::process::exit;
+use std::str::FromStr;
+
+/// The parameters of a day, usually declared with `params!`.
+pub trait Params: Sized {
+    /// The values of the puzzle.
+    fn puzzle() -> Self;
+
+    /// The values for the sample input of the puzzle text, where they differ.
+    fn sample() -> Self;
+
+    /// Sets the option `name`, given without its leading `--`.
+    fn set(&mut self, name: &str, value: &str) -> Result<(), String>;
+
+    /// A line per option, with its default.
+    fn usage() -> String;
+
+    /// The values for the sample or the real input, changed by `options`, given as name and value
+    /// pairs.
+    fn from_options(options: &[(String, String)], sample: bool) -> Result<Self, String> {
+        let mut params = if sample { Self::sample() } else { Self::puzzle() };
+        for (name, value) in options {
+            params.set(name, value)?;
+        }
+        Ok(params)
+    }
+}
+
+/// Parses the value of option `name`.
+pub fn parse_value<T: FromStr>(name: &str, value: &str) -> Result<T, String>
+where
+    T::Err: Display,
+{
+    value.parse().map_err(|e| format!("Invalid value {value} for --{name}: {e}"))
+}
+
+/// A line of `usage`, for an option with its default.
+pub fn usage_line(name: &str, default: impl Display, doc: &str) -> String {
+    format!("  --{:<20} {doc} (default {default})\n", format!("{name} N"))
+}
+
+/// The arguments of a day binary.
+#[derive(Debug, PartialEq)]
+pub struct Args<P> {
+    pub params: P,
+    /// Path to the input, for `input::read`: stdin (`-`) unless one is given.
+    pub input: String,
+}
+
+/// Reads the arguments of a day binary: an optional input path and the options of `P`, besides
+/// `--sample`, to start from the values for the sample input, and `-v`, to turn on the verbose
+/// diagnostics. Prints the usage and exits on anything else.
+pub fn args<P: Params>() -> Args<P> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    from_args(&args).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        eprintln!("Usage: [PATH|-] [OPTIONS]  Input file, .gz and .zst too (default stdin)");
+        eprintln!("Options: -v              Show diagnostics on stderr");
+        eprintln!("         --sample        Use the parameters of the sample input");
+        eprint!("{}", P::usage());
+        exit(2)
+    })
+}
+
+fn from_args<P: Params>(args: &[String]) -> Result<Args<P>, String> {
+    let mut options = Vec::new();
+    let mut sample = false;
+    let mut input = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-v" | "--verbose" => set_verbose(true),
+            "--sample" => sample = true,
+            _ => match arg.strip_prefix("--") {
+                Some(name) => {
+                    let value = args.next().ok_or_else(|| format!("Missing value for {arg}"))?;
+                    options.push((name.to_string(), value.clone()));
+                }
+                None if input.is_none() => input = Some(arg.clone()),
+                None => return Err(format!("Unknown argument {arg}")),
+            },
+        }
+    }
+    let input = input.unwrap_or_else(|| STDIN.to_string());
+    Ok(Args { params: P::from_options(&options, sample)?, input })
+}
+
+/// No parameters, for the days that have none.
+impl Params for () {
+    fn puzzle() {}
+
+    fn sample() {}
+
+    fn set(&mut self, name: &str, _: &str) -> Result<(), String> {
+        Err(format!("Unknown option --{name}"))
+    }
+
+    fn usage() -> String { String::new() }
+}
+
+/// Declares a struct of parameters with their puzzle values, and implements `Params` for it. Each
+/// field becomes an option named after it, with `-` for `_`. The doc comments show in the usage.
+/// Values for the sample input that differ go in a `sample` block after the struct:
+///
+/// ```
+/// aoc_common::params! {
+///     pub struct Params {
+///         /// Rounds to play.
+///         rounds: usize = 10,
+///     }
+///     sample { rounds: 2 }
+/// }
+/// ```
+#[macro_export]
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident {
+            $(
+                $(#[doc = $doc:literal])*
+                $field:ident: $ty:ty = $default:expr,
+            )*
+        }
+        $(sample { $($sample_field:ident: $sample_value:expr),* $(,)? })?
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct $name {
+            $(
+                $(#[doc = $doc])*
+                pub $field: $ty,
+            )*
+        }
+
+        impl $crate::params::Params for $name {
+            fn puzzle() -> $name { $name { $($field: $default,)* } }
+
+            #[allow(unused_mut)]
+            fn sample() -> $name {
+                let mut params = <$name as $crate::params::Params>::puzzle();
+                $($(params.$sample_field = $sample_value;)*)?
+                params
+            }
+
+            fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+                $(
+                    if name == stringify!($field).replace('_', "-") {
+                        self.$field = $crate::params::parse_value(name, value)?;
+                        return Ok(());
+                    }
+                )*
+                Err(format!("Unknown option --{name}"))
+            }
+
+            fn usage() -> String {
+                let mut usage = String::new();
+                let defaults = <$name as $crate::params::Params>::puzzle();
+                $(
+                    let doc: &[&str] = &[$($doc.trim()),*];
+                    usage += &$crate::params::usage_line(
+                        &stringify!($field).replace('_', "-"),
+                        &defaults.$field,
+                        &doc.join(" "),
+                    );
+                )*
+                usage
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    params! {
+        /// For testing.
+        pub struct Test {
+            /// Rounds to play.
+            max_rounds: usize = 10,
+            key: i64 = -3,
+        }
+        sample { key: 4 }
+    }
+
+    fn strings(args: &[&str]) -> Vec<String> { args.iter().map(|s| s.to_string()).collect() }
+
+    #[test]
+    fn test_from_args() {
+        let params = |args: &[&str]| from_args::<Test>(&strings(args)).map(|a| a.params);
+        assert_eq!(params(&[]), Ok(Test { max_rounds: 10, key: -3 }));
+        assert_eq!(
+            params(&["--max-rounds", "20", "--sample"]),
+            Ok(Test { max_rounds: 20, key: 4 })
+        );
+        assert_eq!(
+            params(&["--max-rounds", "x"]),
+            Err(String::from("Invalid value x for --max-rounds: invalid digit found in string"))
+        );
+        assert!(from_args::<Test>(&strings(&["--keys", "1"])).is_err());
+        assert!(from_args::<Test>(&strings(&["--key"])).is_err());
+        assert!(from_args::<()>(&strings(&["--key", "1"])).is_err());
+    }
+
+    #[test]
+    fn test_input_arg() {
+        assert_eq!(from_args::<()>(&[]).unwrap().input, "-");
+        let args = from_args::<Test>(&strings(&["in.txt.gz", "--key", "1"])).unwrap();
+        assert_eq!(
+            args,
+            Args { params: Test { max_rounds: 10, key: 1 }, input: "in.txt.gz".into() }
+        );
+        assert!(from_args::<()>(&strings(&["a.txt", "b.txt"])).is_err());
+    }
+
+    #[test]
+    fn test_usage() {
+        assert_eq!(
+            Test::usage(),
+            "  --max-rounds N         Rounds to play. (default 10)\n  --key N                 (default -3)\n"
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Malformed puzzle input, with the position of the problem. Lines and columns count from 1, and
+/// columns count characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: &'static str,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    /// What was there instead, `None` at the end of a line or of the input.
+    pub found: Option<String>,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: line {}, column {}: expected {}",
+            self.day, self.line, self.column, self.expected
+        )?;
+        match &self.found {
+            Some(found) => write!(f, ", found `{found}`"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl ParseError {
+    /// The error followed by the offending line of `input` with a caret under the column, for
+    /// showing to a human.
+    pub fn report(&self, input: &str) -> String {
+        let Some(text) = input.lines().nth(self.line - 1) else { return self.to_string() };
+        self.report_line(text)
+    }
+
+    /// Like `report`, given only the offending line, for input read a line at a time.
+    pub fn report_line(&self, text: &str) -> String {
+        let number = self.line.to_string();
+        let margin = " ".repeat(number.len());
+        let caret = " ".repeat(self.column - 1);
+        format!("{self}\n{number} | {text}\n{margin} | {caret}^")
+    }
+}
+
+/// A line of puzzle input, remembering where it came from to report errors.
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    pub day: &'static str,
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// The lines of `input`, numbered from 1.
+    pub fn all(day: &'static str, input: &'a str) -> impl Iterator<Item = Line<'a>> {
+        input.lines().enumerate().map(move |(i, text)| Line { day, number: i + 1, text })
+    }
+
+    /// An error at `at`, which must be a part of this line (e.g. a token split off it). Any other
+    /// string, like `""`, points at the end of the line.
+    pub fn error(&self, at: &str, expected: impl Into<String>) -> ParseError {
+        let start = at.as_ptr() as usize;
+        let line_start = self.text.as_ptr() as usize;
+        let (offset, found) = if !at.is_empty()
+            && start >= line_start
+            && start + at.len() <= line_start + self.text.len()
+        {
+            (start - line_start, Some(at.to_string()))
+        } else {
+            (self.text.len(), None)
+        };
+        self.error_at(self.text[..offset].chars().count(), expected, found)
+    }
+
+    /// An error at the character with index `column` (counting from 0).
+    pub fn error_at_char(&self, column: usize, expected: impl Into<String>) -> ParseError {
+        let found = self.text.chars().nth(column).map(String::from);
+        self.error_at(column, expected, found)
+    }
+
+    fn error_at(
+        &self,
+        column: usize,
+        expected: impl Into<String>,
+        found: Option<String>,
+    ) -> ParseError {
+        ParseError {
+            day: self.day,
+            line: self.number,
+            column: column + 1,
+            expected: expected.into(),
+            found,
+        }
+    }
+
+    /// Parses `token`, a part of this line.
+    pub fn parse<T: FromStr>(&self, token: &str, expected: &str) -> Result<T, ParseError> {
+        token.parse().map_err(|_| self.error(token, expected))
+    }
+
+    /// Unwraps a token that may be missing, e.g. from splitting the line, pointing the error at
+    /// the end of the line.
+    pub fn require<T>(&self, token: Option<T>, expected: &str) -> Result<T, ParseError> {
+        token.ok_or_else(|| self.error("", expected))
+    }
+
+    /// Parses the whole line.
+    pub fn parse_all<T: FromStr>(&self, expected: &str) -> Result<T, ParseError> {
+        self.parse(self.text, expected)
+    }
+}
+
+/// An error for input that ends too early.
+pub fn end_of_input(day: &'static str, input: &str, expected: impl Into<String>) -> ParseError {
+    ParseError {
+        day,
+        line: input.lines().count() + 1,
+        column: 1,
+        expected: expected.into(),
+        found: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_position() {
+        let lines: Vec<_> = Line::all("day0", "abc\n1é,x2\n").collect();
+        let line = lines[1];
+        let token = line.text.split(',').nth(1).unwrap();
+        let err = line.parse::<i32>(token, "a number").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+        assert_eq!(err.found.as_deref(), Some("x2"));
+        assert_eq!(err.to_string(), "day0: line 2, column 4: expected a number, found `x2`");
+
+        let err = line.error("", "`;`");
+        assert_eq!((err.line, err.column, err.found), (2, 6, None));
+        assert_eq!(line.error_at_char(1, "a digit").found.as_deref(), Some("é"));
+        assert_eq!(
+            line.require(line.text.split(',').nth(2), "a third field").unwrap_err().column,
+            6
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let input = "1-2,3-4\n5-6,7x8\n";
+        let line = Line::all("day4", input).nth(1).unwrap();
+        let err = line.error(&line.text[5..], "`-`");
+        assert_eq!(
+            err.report(input),
+            "day4: line 2, column 6: expected `-`, found `x8`\n2 | 5-6,7x8\n  |      ^"
+        );
+        let err = end_of_input("day4", input, "more");
+        assert_eq!(err.report(input), err.to_string());
+    }
+
+    #[test]
+    fn test_end_of_input() {
+        let err = end_of_input("day0", "a\nb\n", "more lines");
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.to_string(), "day0: line 3, column 1: expected more lines");
+    }
+}
@@ -0,0 +1,78 @@
+//! Grid states as pictures: a kind per cell and a colour per kind, for saving as images.
+
+use crate::bbox::BBox2;
+use crate::grid::Grid;
+use crate::point::Point2;
+
+/// A colour, as red, green and blue.
+pub type Rgb = [u8; 3];
+
+/// A grid of cells, each drawn in the colour its kind has in the palette.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Picture {
+    /// Where the top left cell is, so that pictures of a growing state can be lined up.
+    pub origin: Point2<i32>,
+    /// Indices into the palette.
+    pub cells: Grid<u8>,
+    /// At most 256 colours.
+    pub palette: Vec<Rgb>,
+}
+
+impl Picture {
+    /// The points of `bounds`, with `f` choosing the kind of each.
+    pub fn draw<F>(bounds: &BBox2<i32>, palette: Vec<Rgb>, mut f: F) -> Picture
+    where
+        F: FnMut(Point2<i32>) -> u8,
+    {
+        assert!(palette.len() <= 256, "Too many colours");
+        let rows = (bounds.min.y..=bounds.max.y)
+            .map(|y| (bounds.min.x..=bounds.max.x).map(|x| f(Point2 { x, y })).collect())
+            .collect();
+        Picture { origin: bounds.min, cells: Grid::from_rows(rows), palette }
+    }
+
+    /// The cells, each drawn as a `scale` by `scale` square.
+    pub fn scale(&self, scale: usize) -> Grid<u8> {
+        let rows = self
+            .cells
+            .rows()
+            .flat_map(|row| {
+                let row: Vec<u8> = row.iter().flat_map(|c| [*c].repeat(scale)).collect();
+                vec![row; scale]
+            })
+            .collect();
+        Grid::from_rows(rows)
+    }
+}
+
+/// Shades from `dark` to `light`, in `n` steps.
+pub fn shades(dark: Rgb, light: Rgb, n: usize) -> Vec<Rgb> {
+    let mix = |i: usize, c: usize| {
+        let t = i as f64 / (n.max(2) - 1) as f64;
+        (dark[c] as f64 + t * (light[c] as f64 - dark[c] as f64)).round() as u8
+    };
+    (0..n).map(|i| [mix(i, 0), mix(i, 1), mix(i, 2)]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draw_and_scale() {
+        let bounds = BBox2 { min: Point2 { x: -1, y: 2 }, max: Point2 { x: 0, y: 3 } };
+        let picture = Picture::draw(&bounds, vec![[0; 3], [255; 3]], |p| (p.x == p.y - 3) as u8);
+        assert_eq!(picture.origin, Point2 { x: -1, y: 2 });
+        let scaled = picture.scale(2);
+        let rows: Vec<&[u8]> = scaled.rows().collect();
+        assert_eq!(rows, [[1, 1, 0, 0], [1, 1, 0, 0], [0, 0, 1, 1], [0, 0, 1, 1]]);
+    }
+
+    #[test]
+    fn test_shades() {
+        assert_eq!(
+            shades([0, 0, 0], [100, 200, 50], 3),
+            [[0, 0, 0], [50, 100, 25], [100, 200, 50]]
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops;
+
+/// Signed integer types usable as point coordinates.
+pub trait Coord:
+    'static
+    + Copy
+    + Debug
+    + Ord
+    + Hash
+    + ops::Add<Output = Self>
+    + ops::Sub<Output = Self>
+    + ops::Mul<Output = Self>
+    + ops::Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+    fn from_usize(n: usize) -> Self;
+    /// Converts to an index, or `None` if the coordinate is negative.
+    fn to_usize(self) -> Option<usize>;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {
+        $(
+            impl Coord for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn abs(self) -> Self { <$t>::abs(self) }
+                fn signum(self) -> Self { <$t>::signum(self) }
+                fn from_usize(n: usize) -> Self { n as $t }
+                fn to_usize(self) -> Option<usize> { usize::try_from(self).ok() }
+            }
+        )*
+    };
+}
+
+impl_coord!(i32, i64, isize);
+
+/// A point in 2D space. Where it matters (directions, rotation) the y axis points down, like
+/// the rows of the puzzle inputs.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Coord> Point2<T> {
+    pub fn new(x: T, y: T) -> Point2<T> { Point2 { x, y } }
+
+    pub fn origin() -> Point2<T> { Point2 { x: T::ZERO, y: T::ZERO } }
+
+    pub fn manhattan(&self, other: &Point2<T>) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Largest distance along a single axis.
+    pub fn chebyshev(&self, other: &Point2<T>) -> T {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    pub fn signum(&self) -> Point2<T> { Point2 { x: self.x.signum(), y: self.y.signum() } }
+
+    /// Moves one step (possibly diagonally) towards `other`.
+    pub fn step_towards(&self, other: &Point2<T>) -> Point2<T> { *self + (*other - *self).signum() }
+
+    /// Rotates a quarter turn counter-clockwise (as seen on screen) around the origin.
+    pub fn rotate_left(&self) -> Point2<T> { Point2 { x: self.y, y: -self.x } }
+
+    /// Rotates a quarter turn clockwise (as seen on screen) around the origin.
+    pub fn rotate_right(&self) -> Point2<T> { Point2 { x: -self.y, y: self.x } }
+
+    /// The four orthogonally adjacent points.
+    pub fn neighbours4(&self) -> impl Iterator<Item = Point2<T>> {
+        let p = *self;
+        crate::Dir4::ALL.into_iter().map(move |d| p + d.delta())
+    }
+
+    /// The eight orthogonally and diagonally adjacent points.
+    pub fn neighbours8(&self) -> impl Iterator<Item = Point2<T>> {
+        let p = *self;
+        crate::Dir8::ALL.into_iter().map(move |d| p + d.delta())
+    }
+}
+
+impl<T: Coord> ops::Add for Point2<T> {
+    type Output = Point2<T>;
+    fn add(self, rhs: Point2<T>) -> Self::Output { Point2 { x: self.x + rhs.x, y: self.y + rhs.y } }
+}
+
+impl<T: Coord> ops::AddAssign for Point2<T> {
+    fn add_assign(&mut self, rhs: Point2<T>) { *self = *self + rhs }
+}
+
+impl<T: Coord> ops::Sub for Point2<T> {
+    type Output = Point2<T>;
+    fn sub(self, rhs: Point2<T>) -> Self::Output { Point2 { x: self.x - rhs.x, y: self.y - rhs.y } }
+}
+
+impl<T: Coord> ops::Mul<T> for Point2<T> {
+    type Output = Point2<T>;
+    fn mul(self, rhs: T) -> Self::Output { Point2 { x: self.x * rhs, y: self.y * rhs } }
+}
+
+impl<T: Coord> ops::Neg for Point2<T> {
+    type Output = Point2<T>;
+    fn neg(self) -> Self::Output { Point2 { x: -self.x, y: -self.y } }
+}
+
+/// A point in 3D space.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Coord> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Point3<T> { Point3 { x, y, z } }
+
+    pub fn manhattan(&self, other: &Point3<T>) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    /// The six points sharing a face with this one.
+    pub fn neighbours6(&self) -> impl Iterator<Item = Point3<T>> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        let p = *self;
+        [
+            Point3 { x: zero, y: zero, z: one },
+            Point3 { x: zero, y: zero, z: -one },
+            Point3 { x: zero, y: one, z: zero },
+            Point3 { x: zero, y: -one, z: zero },
+            Point3 { x: one, y: zero, z: zero },
+            Point3 { x: -one, y: zero, z: zero },
+        ]
+        .into_iter()
+        .map(move |d| p + d)
+    }
+}
+
+impl<T: Coord> ops::Add for Point3<T> {
+    type Output = Point3<T>;
+    fn add(self, rhs: Point3<T>) -> Self::Output {
+        Point3 { x: self.x + rhs.x, y: self.y + rhs.y, z: self.z + rhs.z }
+    }
+}
+
+impl<T: Coord> ops::Sub for Point3<T> {
+    type Output = Point3<T>;
+    fn sub(self, rhs: Point3<T>) -> Self::Output {
+        Point3 { x: self.x - rhs.x, y: self.y - rhs.y, z: self.z - rhs.z }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_towards() {
+        let p = Point2 { x: 10, y: 10 };
+        assert_eq!(p.step_towards(&Point2 { x: 20, y: 10 }), Point2 { x: 11, y: 10 });
+        assert_eq!(p.step_towards(&Point2 { x: 2, y: 10 }), Point2 { x: 9, y: 10 });
+        assert_eq!(p.step_towards(&Point2 { x: 12, y: 1 }), Point2 { x: 11, y: 9 });
+        assert_eq!(p.step_towards(&p), p);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let east = Point2 { x: 1, y: 0 };
+        assert_eq!(east.rotate_left(), Point2 { x: 0, y: -1 });
+        assert_eq!(east.rotate_right(), Point2 { x: 0, y: 1 });
+        assert_eq!(east.rotate_left().rotate_right(), east);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let p = Point2 { x: 0i32, y: 0 };
+        assert_eq!(p.neighbours4().count(), 4);
+        assert!(p.neighbours4().all(|n| n.manhattan(&p) == 1));
+        assert_eq!(p.neighbours8().count(), 8);
+        assert!(p.neighbours8().all(|n| n.chebyshev(&p) == 1));
+
+        let p = Point3 { x: 1i32, y: 2, z: 3 };
+        assert_eq!(p.neighbours6().count(), 6);
+        assert!(p.neighbours6().all(|n| n.manhattan(&p) == 1));
+    }
+}
@@ -0,0 +1,242 @@
+//! Graph searches over nodes produced on the fly by a successor function.
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+use std::ops::Add;
+
+/// How much work a search did.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    /// Nodes whose successors were generated.
+    pub expanded: usize,
+    /// Distinct nodes seen, including the start.
+    pub discovered: usize,
+    /// Largest number of nodes waiting to be expanded at once.
+    pub max_frontier: usize,
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} expanded, {} discovered, frontier up to {}",
+            self.expanded, self.discovered, self.max_frontier
+        )
+    }
+}
+
+/// A shortest path to a goal.
+#[derive(Debug, Clone)]
+pub struct Found<N, C> {
+    pub dist: C,
+    /// From the start to the goal, both included.
+    pub path: Vec<N>,
+    pub stats: Stats,
+}
+
+/// Everything reachable from the start, with its distance in steps.
+#[derive(Debug, Clone)]
+pub struct Reached<N> {
+    pub dist: HashMap<N, usize>,
+    pub stats: Stats,
+}
+
+/// The nodes seen so far, each with the index of the node it was reached from and its distance.
+struct Tree<N, C> {
+    nodes: Vec<(N, Option<usize>, C)>,
+    index: HashMap<N, usize>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Tree<N, C> {
+    fn new(start: N, zero: C) -> Tree<N, C> {
+        Tree { nodes: vec![(start.clone(), None, zero)], index: HashMap::from([(start, 0)]) }
+    }
+
+    fn path(&self, mut i: usize) -> Vec<N> {
+        let mut path = vec![self.nodes[i].0.clone()];
+        while let Some(parent) = self.nodes[i].1 {
+            path.push(self.nodes[parent].0.clone());
+            i = parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Breadth first search, stopping at the first node found (if any) for which `goal` holds.
+fn breadth_first<N, S, I, G>(
+    start: N,
+    mut successors: S,
+    mut goal: G,
+) -> (Tree<N, usize>, Option<usize>, Stats)
+where
+    N: Clone + Eq + Hash,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    G: FnMut(&N) -> bool,
+{
+    let mut tree = Tree::new(start, 0);
+    let mut stats = Stats { discovered: 1, max_frontier: 1, ..Stats::default() };
+    let mut queue = VecDeque::from([0]);
+    while let Some(i) = queue.pop_front() {
+        if goal(&tree.nodes[i].0) {
+            return (tree, Some(i), stats);
+        }
+        stats.expanded += 1;
+        let dist = tree.nodes[i].2 + 1;
+        for next in successors(&tree.nodes[i].0) {
+            if let Entry::Vacant(e) = tree.index.entry(next) {
+                let j = tree.nodes.len();
+                tree.nodes.push((e.key().clone(), Some(i), dist));
+                e.insert(j);
+                queue.push_back(j);
+            }
+        }
+        stats.discovered = tree.nodes.len();
+        stats.max_frontier = stats.max_frontier.max(queue.len());
+    }
+    (tree, None, stats)
+}
+
+/// Fewest steps from `start` to a node for which `goal` holds.
+pub fn bfs<N, S, I, G>(start: N, successors: S, goal: G) -> Option<Found<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    G: FnMut(&N) -> bool,
+{
+    let (tree, found, stats) = breadth_first(start, successors, goal);
+    found.map(|i| Found { dist: tree.nodes[i].2, path: tree.path(i), stats })
+}
+
+/// Fewest steps from `start` to every node reachable from it.
+pub fn flood<N, S, I>(start: N, successors: S) -> Reached<N>
+where
+    N: Clone + Eq + Hash,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let (tree, _, stats) = breadth_first(start, successors, |_| false);
+    Reached { dist: tree.nodes.into_iter().map(|(node, _, dist)| (node, dist)).collect(), stats }
+}
+
+/// Cheapest path from `start` to a node for which `goal` holds, where `successors` gives the
+/// cost of each step.
+pub fn dijkstra<N, C, S, I, G>(start: N, successors: S, goal: G) -> Option<Found<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    G: FnMut(&N) -> bool,
+{
+    astar(start, successors, |_| C::default(), goal)
+}
+
+/// Like `dijkstra`, but guided by `heuristic`, an estimate of the cost left to reach a goal. The
+/// path is the cheapest as long as the estimate never exceeds the real cost.
+pub fn astar<N, C, S, I, H, G>(
+    start: N,
+    mut successors: S,
+    mut heuristic: H,
+    mut goal: G,
+) -> Option<Found<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: FnMut(&N) -> C,
+    G: FnMut(&N) -> bool,
+{
+    let zero = C::default();
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), zero, 0))]);
+    let mut tree = Tree::new(start, zero);
+    let mut stats = Stats { discovered: 1, max_frontier: 1, ..Stats::default() };
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        if cost > tree.nodes[i].2 {
+            // Reached more cheaply since it was queued.
+            continue;
+        }
+        if goal(&tree.nodes[i].0) {
+            return Some(Found { dist: cost, path: tree.path(i), stats });
+        }
+        stats.expanded += 1;
+        for (next, step) in successors(&tree.nodes[i].0) {
+            let cost = cost + step;
+            let j = match tree.index.entry(next) {
+                Entry::Occupied(e) if cost < tree.nodes[*e.get()].2 => {
+                    let j = *e.get();
+                    tree.nodes[j].1 = Some(i);
+                    tree.nodes[j].2 = cost;
+                    j
+                }
+                Entry::Occupied(_) => continue,
+                Entry::Vacant(e) => {
+                    let j = tree.nodes.len();
+                    tree.nodes.push((e.key().clone(), Some(i), cost));
+                    e.insert(j);
+                    j
+                }
+            };
+            heap.push(Reverse((cost + heuristic(&tree.nodes[j].0), cost, j)));
+        }
+        stats.discovered = tree.nodes.len();
+        stats.max_frontier = stats.max_frontier.max(heap.len());
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Steps along a line, skipping the multiples of 5 except 0.
+    fn line(n: &i32) -> Vec<i32> {
+        [n - 1, n + 1].into_iter().filter(|m| m % 5 != 0 || *m == 0).collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let found = bfs(0, line, |n| *n == 4).unwrap();
+        assert_eq!(found.dist, 4);
+        assert_eq!(found.path, vec![0, 1, 2, 3, 4]);
+        assert!(bfs(0, line, |n| *n == 6).is_none());
+        assert_eq!(bfs(3, line, |n| *n == 3).unwrap().path, vec![3]);
+    }
+
+    #[test]
+    fn test_flood() {
+        let reached = flood(2, line);
+        assert_eq!(reached.dist.len(), 9);
+        assert_eq!(reached.dist[&-4], 6);
+        assert_eq!(reached.stats.expanded, 9);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // Going up costs 1, jumping from 0 to 10 costs 3.
+        let successors = |n: &i32| if *n == 0 { vec![(1, 1), (10, 3)] } else { vec![(n + 1, 1)] };
+        let found = dijkstra(0, successors, |n| *n == 11).unwrap();
+        assert_eq!(found.dist, 4);
+        assert_eq!(found.path, vec![0, 10, 11]);
+    }
+
+    #[test]
+    fn test_astar() {
+        // A grid without walls, where Manhattan distance is exact.
+        let successors =
+            |&(x, y): &(i32, i32)| [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)].map(|p| (p, 1));
+        let heuristic = |&(x, y): &(i32, i32)| (x - 5).abs() + (y - 3).abs();
+        let found = astar((0, 0), successors, heuristic, |p| *p == (5, 3)).unwrap();
+        assert_eq!(found.dist, 8);
+        assert_eq!(found.path.len(), 9);
+        let plain = dijkstra((0, 0), successors, |p| *p == (5, 3)).unwrap();
+        assert_eq!(plain.dist, 8);
+        assert!(found.stats.expanded < plain.stats.expanded);
+    }
+}
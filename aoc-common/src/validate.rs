@@ -0,0 +1,45 @@
+//! What the solutions assume about their input beyond its format, like a map having exactly one
+//! start. Parsing only checks the format; a day's `validate` checks the rest of what its parts
+//! rely on, to say which assumption an input breaks instead of panicking or giving a wrong answer.
+
+use std::error::Error;
+use std::fmt;
+
+/// An assumption of a day's solution that the input doesn't hold to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub day: &'static str,
+    /// What the solution assumes, e.g. "exactly one `S` on the map".
+    pub assumption: String,
+    /// What the input has instead, e.g. "3".
+    pub found: String,
+}
+
+impl Violation {
+    pub fn new(
+        day: &'static str,
+        assumption: impl Into<String>,
+        found: impl Into<String>,
+    ) -> Violation {
+        Violation { day, assumption: assumption.into(), found: found.into() }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: assumes {}, found {}", self.day, self.assumption, self.found)
+    }
+}
+
+impl Error for Violation {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let violation = Violation::new("day12", "exactly one `S` on the map", "2");
+        assert_eq!(violation.to_string(), "day12: assumes exactly one `S` on the map, found 2");
+    }
+}
@@ -0,0 +1,84 @@
+//! Diagnostic output that is kept apart from the answers: it goes to stderr, and only when
+//! enabled, so that the answers on stdout stay easy to parse.
+//!
+//! Besides free-form lines (`verbose!`), long runs report structured progress: a `Span` around
+//! a stretch of work like a part of a day, printed when it starts and with its elapsed time when
+//! it ends, and events with named values (`event!`) from inside it, e.g.
+//! ```text
+//! day16 part 2
+//!   subsets done=4096 total=32768
+//! day16 part 2 elapsed=1.21s
+//! ```
+//! Lines are indented by the spans they are in, on the thread printing them.
+
+use std::cell::Cell;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// Spans entered and not yet ended on this thread.
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+pub fn set_verbose(on: bool) { VERBOSE.store(on, Ordering::Relaxed) }
+
+pub fn is_verbose() -> bool { VERBOSE.load(Ordering::Relaxed) }
+
+/// Prints a line to stderr, indented by the spans it is in. Only for the macros and `Span`,
+/// which check `is_verbose` first.
+pub fn print(args: fmt::Arguments) {
+    let depth = DEPTH.with(Cell::get);
+    eprintln!("{:width$}{args}", "", width = 2 * depth);
+}
+
+/// A stretch of work, from `span` until dropped. Nothing is kept or printed unless verbose.
+#[must_use = "a span ends when dropped"]
+pub struct Span(Option<(String, Instant)>);
+
+/// Starts a span named `name`, e.g. `day19 part 1`.
+pub fn span(name: impl fmt::Display) -> Span {
+    if !is_verbose() {
+        return Span(None);
+    }
+    let name = name.to_string();
+    print(format_args!("{name}"));
+    DEPTH.with(|depth| depth.set(depth.get() + 1));
+    Span(Some((name, Instant::now())))
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some((name, start)) = &self.0 {
+            DEPTH.with(|depth| depth.set(depth.get() - 1));
+            print(format_args!("{name} elapsed={:.2?}", start.elapsed()));
+        }
+    }
+}
+
+/// Like `eprintln!`, but only prints after `set_verbose(true)`.
+#[macro_export]
+macro_rules! verbose {
+    ($($arg:tt)*) => {
+        if $crate::verbose::is_verbose() {
+            $crate::verbose::print(format_args!($($arg)*));
+        }
+    };
+}
+
+/// An event with named values, e.g. `event!("cycle", start = 15, period = 35)` printing
+/// `cycle start=15 period=35`, but only after `set_verbose(true)`. The values are only evaluated
+/// then.
+#[macro_export]
+macro_rules! event {
+    ($name:literal $(, $key:ident = $value:expr)* $(,)?) => {
+        if $crate::verbose::is_verbose() {
+            $crate::verbose::print(format_args!(
+                concat!($name $(, " ", stringify!($key), "={}")*)
+                $(, $value)*
+            ));
+        }
+    };
+}
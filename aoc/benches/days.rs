@@ -0,0 +1,96 @@
+//! Times the parse step and both parts of each day and compares them against the saved baseline.
+//!
+//!     cargo bench -p aoc -- [dayN...] [--input test|input] [--save] [--threshold PCT]
+//!
+//! Without days, all of them are run. `--save` stores the times as the new baseline (kept under
+//! `target/`, per input). Otherwise any stage that got slower than the baseline by more than the
+//! threshold (10% by default) is flagged, and the bench fails.
+
+use aoc::bench::{change, Baseline, Bencher};
+use aoc::DAYS;
+use std::env::args;
+use std::path::PathBuf;
+use std::process::exit;
+use std::time::Duration;
+
+/// Time spent repeating each stage, to get a stable median.
+const BUDGET: Duration = Duration::from_secs(1);
+
+fn fail(msg: &str) -> ! {
+    eprintln!("{msg}");
+    exit(2)
+}
+
+fn main() {
+    let mut names = Vec::new();
+    let mut input = String::from("input");
+    let mut save = false;
+    let mut threshold = 10.0;
+
+    let mut args = args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // Passed by `cargo bench`.
+            "--bench" => {}
+            "--save" => save = true,
+            "--input" => match args.next() {
+                Some(value) if value == "test" || value == "input" => input = value,
+                _ => fail("--input takes test or input"),
+            },
+            "--threshold" => match args.next().and_then(|v| v.parse::<f64>().ok()) {
+                Some(value) => threshold = value,
+                None => fail("--threshold takes a percentage"),
+            },
+            name if aoc::find(name).is_some() => names.push(arg),
+            _ => fail(&format!("Unknown argument {arg}")),
+        }
+    }
+
+    let path: PathBuf =
+        [env!("CARGO_MANIFEST_DIR"), "..", "target", "aoc-bench", &format!("{input}.tsv")]
+            .iter()
+            .collect();
+    let mut baseline = Baseline::load(&path).unwrap_or_else(|e| fail(&e.to_string()));
+
+    let mut regressions = 0;
+    for day in DAYS.iter().filter(|day| names.is_empty() || names.iter().any(|n| n == day.name)) {
+        let text = day.load_input(&input).unwrap_or_else(|e| fail(&e.to_string()));
+        let mut bencher = Bencher::new(BUDGET);
+        if let Err(e) = (day.bench)(&text, &mut bencher) {
+            eprintln!("{}", e.report(&text.text));
+            exit(1);
+        }
+
+        for timing in bencher.finish() {
+            let compared = match baseline.get(day.name, timing.stage) {
+                Some(before) if !save => {
+                    let change = 100.0 * change(before, timing.median);
+                    let flag = if change > threshold {
+                        regressions += 1;
+                        "  REGRESSED"
+                    } else {
+                        ""
+                    };
+                    format!("{before:.2?} -> {:+.1}%{flag}", change)
+                }
+                _ => String::new(),
+            };
+            let line = format!(
+                "{:<6} {:<6} {:>12.2?} {:>6} runs  {compared}",
+                day.name, timing.stage, timing.median, timing.runs
+            );
+            println!("{}", line.trim_end());
+            if save {
+                baseline.insert(day.name, timing.stage, timing.median);
+            }
+        }
+    }
+
+    if save {
+        baseline.save(&path).unwrap_or_else(|e| fail(&e.to_string()));
+        println!("Saved baseline to {}", path.display());
+    } else if regressions > 0 {
+        eprintln!("{regressions} stage(s) regressed by more than {threshold}%");
+        exit(1);
+    }
+}
@@ -0,0 +1,188 @@
+//! Plays the animation of a day in the terminal, a step per frame.
+//!
+//! The terminal is left in its usual line mode, so the controls are lines read from stdin:
+//! Enter pauses, and steps a frame forward while paused; `c` continues, `+` and `-` double and
+//! halve the speed, and `q` quits.
+
+use aoc_common::animate::Animation;
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+/// Moves the cursor to the top left corner of the terminal.
+const HOME: &str = "\x1b[H";
+/// Clears from the cursor to the end of the line, or of the screen.
+const CLEAR_LINE: &str = "\x1b[K";
+const CLEAR_BELOW: &str = "\x1b[J";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Control {
+    /// Enter, which pauses or steps.
+    Step,
+    Continue,
+    Faster,
+    Slower,
+    Quit,
+}
+
+impl Control {
+    fn parse(line: &str) -> Option<Control> {
+        match line.trim() {
+            "" => Some(Control::Step),
+            "c" => Some(Control::Continue),
+            "+" => Some(Control::Faster),
+            "-" => Some(Control::Slower),
+            "q" => Some(Control::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// The lines typed on stdin, read on a thread of their own.
+pub fn stdin_controls() -> Receiver<String> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines().map_while(Result::ok) {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+fn draw<W: Write>(out: &mut W, frame: &str, status: &str) -> io::Result<()> {
+    write!(out, "{HOME}")?;
+    for line in frame.lines() {
+        writeln!(out, "{line}{CLEAR_LINE}")?;
+    }
+    write!(out, "{status}{CLEAR_LINE}\n{CLEAR_BELOW}")?;
+    out.flush()
+}
+
+/// Plays `animation` at `fps` frames per second until it ends or `q` comes on `controls`.
+/// Returns the number of steps played.
+pub fn play<W: Write>(
+    animation: &mut dyn Animation,
+    mut fps: f64,
+    controls: &Receiver<String>,
+    out: &mut W,
+) -> io::Result<usize> {
+    let mut steps = 0;
+    let mut paused = false;
+    // Without controls (stdin closed), keep playing without pausing.
+    let mut open = true;
+    loop {
+        let status = if paused {
+            format!("Step {steps}, paused. Enter: step, c: continue, q: quit")
+        } else {
+            format!("Step {steps}, {fps} fps. Enter: pause, +/-: speed, q: quit")
+        };
+        draw(out, &animation.frame(), &status)?;
+
+        let delay = Duration::from_secs_f64(1.0 / fps);
+        let line = if !open {
+            thread::sleep(delay);
+            None
+        } else if paused {
+            match controls.recv() {
+                Ok(line) => Some(line),
+                Err(_) => return Ok(steps),
+            }
+        } else {
+            match controls.recv_timeout(delay) {
+                Ok(line) => Some(line),
+                Err(RecvTimeoutError::Timeout) => None,
+                Err(RecvTimeoutError::Disconnected) => {
+                    open = false;
+                    None
+                }
+            }
+        };
+
+        let advance = match line.as_deref().map(Control::parse) {
+            None => true,
+            Some(Some(Control::Step)) if paused => true,
+            Some(Some(Control::Step)) => {
+                paused = true;
+                false
+            }
+            Some(Some(Control::Continue)) => {
+                paused = false;
+                false
+            }
+            Some(Some(Control::Faster)) => {
+                fps *= 2.0;
+                false
+            }
+            Some(Some(Control::Slower)) => {
+                fps /= 2.0;
+                false
+            }
+            Some(Some(Control::Quit)) => return Ok(steps),
+            Some(None) => false,
+        };
+        if advance {
+            if !animation.step() {
+                draw(out, &animation.frame(), &format!("Done after {steps} steps"))?;
+                return Ok(steps);
+            }
+            steps += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts up to a limit.
+    struct Counter(usize, usize);
+
+    impl Animation for Counter {
+        fn frame(&self) -> String { format!("{}\n", self.0) }
+
+        fn step(&mut self) -> bool {
+            if self.0 == self.1 {
+                return false;
+            }
+            self.0 += 1;
+            true
+        }
+    }
+
+    fn play_with(limit: usize, lines: &[&str]) -> (usize, usize, String) {
+        let (sender, receiver) = mpsc::channel();
+        for line in lines {
+            sender.send(line.to_string()).unwrap();
+        }
+        drop(sender);
+        let mut counter = Counter(0, limit);
+        let mut out = Vec::new();
+        let steps = play(&mut counter, 1000.0, &receiver, &mut out).unwrap();
+        (steps, counter.0, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_plays_to_the_end() {
+        let (steps, count, out) = play_with(3, &[]);
+        assert_eq!((steps, count), (3, 3));
+        assert!(out.ends_with(&format!(
+            "{HOME}3{CLEAR_LINE}\nDone after 3 steps{CLEAR_LINE}\n{CLEAR_BELOW}"
+        )));
+    }
+
+    #[test]
+    fn test_quit() {
+        assert_eq!(play_with(3, &["q"]).0, 0);
+    }
+
+    #[test]
+    fn test_pause_and_step() {
+        // Pause, step twice, ignore `x`, then quit.
+        let (steps, count, out) = play_with(10, &["", "", "", "x", "q"]);
+        assert_eq!((steps, count), (2, 2));
+        assert!(out.contains("Step 2, paused"));
+    }
+}
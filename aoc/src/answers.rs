@@ -0,0 +1,119 @@
+//! The answers manifest kept with each day in `answers.txt`.
+//!
+//! Every answer is recorded as `<input> <part>: <answer>`, e.g. `test 1: 24000`. Answers spanning
+//! several lines (like day 10's screen) leave the first line empty and put each line of the answer
+//! on a line of its own, starting with `| `.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Recorded answers of a day, keyed by input kind (`test`, `input`) and part.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Manifest {
+    answers: BTreeMap<(String, u8), String>,
+}
+
+impl Manifest {
+    pub fn parse(text: &str) -> Result<Manifest, String> {
+        let mut manifest = Manifest::default();
+        let mut last = None;
+        for (i, line) in text.lines().enumerate() {
+            let lineno = i + 1;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(rest) = line.strip_prefix('|') {
+                let Some(key) = &last else {
+                    return Err(format!("line {lineno}: continuation without an answer"));
+                };
+                let answer = manifest.answers.get_mut(key).unwrap();
+                answer.push_str(rest.strip_prefix(' ').unwrap_or(rest));
+                answer.push('\n');
+                continue;
+            }
+            let Some((key, answer)) = line.split_once(':') else {
+                return Err(format!("line {lineno}: expected `<input> <part>: <answer>`"));
+            };
+            let key = match key.split_whitespace().collect::<Vec<_>>()[..] {
+                [input, "1"] => (input.to_string(), 1),
+                [input, "2"] => (input.to_string(), 2),
+                _ => return Err(format!("line {lineno}: invalid key `{key}`")),
+            };
+            if manifest.answers.insert(key.clone(), answer.trim().to_string()).is_some() {
+                return Err(format!("line {lineno}: duplicate answer for {} {}", key.0, key.1));
+            }
+            last = Some(key);
+        }
+        Ok(manifest)
+    }
+
+    /// The recorded answer for `part` on `input`, if any.
+    pub fn get(&self, input: &str, part: u8) -> Option<&str> {
+        self.answers.get(&(input.to_string(), part)).map(|s| s.as_str())
+    }
+
+    pub fn insert(&mut self, input: &str, part: u8, answer: &str) {
+        self.answers.insert((input.to_string(), part), normalize(answer));
+    }
+}
+
+/// Puts an answer in the form it is recorded in: single lines trimmed, and every line of a
+/// multi-line answer terminated by a newline.
+pub fn normalize(answer: &str) -> String {
+    if answer.trim_end().contains('\n') {
+        answer.trim_end().lines().map(|line| format!("{line}\n")).collect()
+    } else {
+        answer.trim().to_string()
+    }
+}
+
+impl fmt::Display for Manifest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Samples first, like the puzzle text.
+        let mut keys: Vec<_> = self.answers.keys().collect();
+        keys.sort_by_key(|(input, part)| (input != "test", input.clone(), *part));
+        for key in keys {
+            let answer = &self.answers[key];
+            if answer.contains('\n') {
+                writeln!(f, "{} {}:", key.0, key.1)?;
+                for line in answer.lines() {
+                    writeln!(f, "| {line}")?;
+                }
+            } else {
+                writeln!(f, "{} {}: {}", key.0, key.1, answer)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let text = "test 1: 13140\ntest 2:\n| ##..\n| #..#\ninput 1: -3\n";
+        let manifest = Manifest::parse(text).unwrap();
+        assert_eq!(manifest.get("test", 1), Some("13140"));
+        assert_eq!(manifest.get("test", 2), Some("##..\n#..#\n"));
+        assert_eq!(manifest.get("input", 1), Some("-3"));
+        assert_eq!(manifest.get("input", 2), None);
+        assert_eq!(manifest.to_string(), text);
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize(" 42\n"), "42");
+        assert_eq!(normalize("##..\n#..#"), "##..\n#..#\n");
+        assert_eq!(normalize("##..\n#..#\n\n"), "##..\n#..#\n");
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(Manifest::parse("| dangling").is_err());
+        assert!(Manifest::parse("test 3: 1").is_err());
+        assert!(Manifest::parse("test 1 42").is_err());
+        assert!(Manifest::parse("test 1: 1\ntest 1: 2").is_err());
+    }
+}
@@ -0,0 +1,128 @@
+//! Timing of the parse step and both parts of each day, compared against a saved baseline.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Upper bound on the runs of a single stage, so that fast stages finish quickly too.
+const MAX_RUNS: usize = 1000;
+
+/// The median time of one stage (`parse`, `part1` or `part2`) of a day.
+#[derive(Debug, Clone)]
+pub struct Timing {
+    pub stage: &'static str,
+    pub median: Duration,
+    pub runs: usize,
+}
+
+/// Runs each stage repeatedly until its time budget is used up, and at least once.
+pub struct Bencher {
+    budget: Duration,
+    timings: Vec<Timing>,
+}
+
+impl Bencher {
+    pub fn new(budget: Duration) -> Bencher { Bencher { budget, timings: Vec::new() } }
+
+    pub fn run<T, F>(&mut self, stage: &'static str, mut f: F)
+    where
+        F: FnMut() -> T,
+    {
+        let mut times = Vec::new();
+        let start = Instant::now();
+        while times.is_empty() || (start.elapsed() < self.budget && times.len() < MAX_RUNS) {
+            let run = Instant::now();
+            black_box(f());
+            times.push(run.elapsed());
+        }
+        times.sort();
+        self.timings.push(Timing { stage, median: times[times.len() / 2], runs: times.len() });
+    }
+
+    pub fn finish(self) -> Vec<Timing> { self.timings }
+}
+
+/// Median times of an earlier run, keyed by day and stage.
+#[derive(Debug, Default, Clone)]
+pub struct Baseline {
+    times: BTreeMap<(String, String), Duration>,
+}
+
+impl Baseline {
+    /// Reads a baseline saved by `save`, empty if there is none yet.
+    pub fn load(path: &Path) -> io::Result<Baseline> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Baseline::default()),
+            Err(e) => return Err(e),
+        };
+        let mut baseline = Baseline::default();
+        for line in text.lines() {
+            let invalid =
+                || io::Error::new(io::ErrorKind::InvalidData, format!("bad line {line:?}"));
+            let [day, stage, nanos] = line.split('\t').collect::<Vec<_>>()[..] else {
+                return Err(invalid());
+            };
+            let nanos = nanos.parse().map_err(|_| invalid())?;
+            baseline
+                .times
+                .insert((day.to_string(), stage.to_string()), Duration::from_nanos(nanos));
+        }
+        Ok(baseline)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text: String = self
+            .times
+            .iter()
+            .map(|((day, stage), time)| format!("{day}\t{stage}\t{}\n", time.as_nanos()))
+            .collect();
+        fs::write(path, text)
+    }
+
+    pub fn get(&self, day: &str, stage: &str) -> Option<Duration> {
+        self.times.get(&(day.to_string(), stage.to_string())).copied()
+    }
+
+    pub fn insert(&mut self, day: &str, stage: &str, time: Duration) {
+        self.times.insert((day.to_string(), stage.to_string()), time);
+    }
+}
+
+/// Relative change from `before` to `after`, e.g. `0.25` for 25% slower.
+pub fn change(before: Duration, after: Duration) -> f64 {
+    after.as_secs_f64() / before.as_secs_f64().max(f64::MIN_POSITIVE) - 1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let path = std::env::temp_dir().join(format!("aoc-baseline-{}.tsv", std::process::id()));
+        let mut baseline = Baseline::default();
+        baseline.insert("day1", "parse", Duration::from_micros(12));
+        baseline.insert("day1", "part2", Duration::from_millis(3));
+        baseline.save(&path).unwrap();
+
+        let loaded = Baseline::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.get("day1", "parse"), Some(Duration::from_micros(12)));
+        assert_eq!(loaded.get("day1", "part2"), Some(Duration::from_millis(3)));
+        assert_eq!(loaded.get("day1", "part1"), None);
+    }
+
+    #[test]
+    fn test_change() {
+        let ms = Duration::from_millis;
+        assert!((change(ms(100), ms(125)) - 0.25).abs() < 1e-9);
+        assert!((change(ms(100), ms(50)) + 0.5).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,600 @@
+//! Random puzzle inputs of any size, for stress testing the days beyond their real input.
+//!
+//! Every generator takes a seeded `Rng` and a `size`, whose meaning depends on the day (the
+//! number of lines, elves, cubes, the side of a map, ...), and returns an input in exactly the
+//! format of the real one. Inputs are valid for the parsers and hold to what the days' `validate`
+//! checks, so the runner solves them; some puzzles additionally promise properties random inputs
+//! don't have (like day 15's single uncovered position), so not every part has an answer on every
+//! generated input.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// A small, seeded pseudo-random generator (SplitMix64), so that a seed always gives the same
+/// input.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng { Rng(seed) }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `lo` to `hi`, both included.
+    pub fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        lo + (self.next_u64() % (hi - lo + 1) as u64) as i64
+    }
+
+    /// An index below `n`, which must not be 0.
+    pub fn below(&mut self, n: usize) -> usize { (self.next_u64() % n as u64) as usize }
+
+    /// True once in `n` times.
+    pub fn one_in(&mut self, n: usize) -> bool { self.below(n) == 0 }
+
+    pub fn pick<T: Copy>(&mut self, items: &[T]) -> T { items[self.below(items.len())] }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+const LOWER: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+fn word(rng: &mut Rng, len: usize) -> String { (0..len).map(|_| rng.pick(LOWER) as char).collect() }
+
+/// `size` elves carrying up to 6 snacks each.
+pub fn day1(rng: &mut Rng, size: usize) -> String {
+    let elves: Vec<String> = (0..size.max(1))
+        .map(|_| (0..rng.range(1, 6)).map(|_| format!("{}\n", rng.range(1000, 60000))).collect())
+        .collect();
+    elves.join("\n")
+}
+
+/// `size` rounds.
+pub fn day2(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| format!("{} {}\n", rng.pick(&['A', 'B', 'C']), rng.pick(&['X', 'Y', 'Z'])))
+        .collect()
+}
+
+/// `size` rucksacks, rounded up to whole groups of three. Each shares exactly one item between
+/// its compartments and exactly one, the badge, with the rest of its group.
+pub fn day3(rng: &mut Rng, size: usize) -> String {
+    let letters: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+    let mut out = String::new();
+    for _ in 0..size.max(1).div_ceil(3) {
+        let mut items = letters.clone();
+        rng.shuffle(&mut items);
+        let badge = items.pop().unwrap();
+        // Each elf has its own 17 items, so the badge is the only one common to all three.
+        for own in items.chunks(17) {
+            let (shared, rest) = own.split_first().unwrap();
+            let (left, right) = rest.split_at(rest.len() / 2);
+            let extra = rng.range(0, 10) as usize;
+            let mut first: Vec<char> = vec![*shared, badge];
+            first.extend((0..extra).map(|_| rng.pick(left)));
+            let mut second: Vec<char> = vec![*shared];
+            second.extend((0..=extra).map(|_| rng.pick(right)));
+            rng.shuffle(&mut first);
+            rng.shuffle(&mut second);
+            writeln!(out, "{}{}", String::from_iter(first), String::from_iter(second)).unwrap();
+        }
+    }
+    out
+}
+
+/// `size` pairs of section assignments.
+pub fn day4(rng: &mut Rng, size: usize) -> String {
+    let range = |rng: &mut Rng| {
+        let start = rng.range(1, 99);
+        format!("{}-{}", start, rng.range(start, 99))
+    };
+    (0..size.max(1)).map(|_| format!("{},{}\n", range(rng), range(rng))).collect()
+}
+
+/// Nine stacks and `size` moves, all of which can be done.
+pub fn day5(rng: &mut Rng, size: usize) -> String {
+    let mut stacks: Vec<Vec<char>> = (0..9)
+        .map(|_| {
+            (0..rng.range(1, 8))
+                .map(|_| rng.range(b'A' as i64, b'Z' as i64) as u8 as char)
+                .collect()
+        })
+        .collect();
+
+    let mut out = String::new();
+    let height = stacks.iter().map(|s| s.len()).max().unwrap();
+    for level in (0..height).rev() {
+        let row: Vec<String> = stacks
+            .iter()
+            .map(|s| s.get(level).map_or(String::from("   "), |c| format!("[{c}]")))
+            .collect();
+        writeln!(out, "{}", row.join(" ")).unwrap();
+    }
+    let numbers: Vec<String> = (1..=stacks.len()).map(|i| format!(" {i} ")).collect();
+    writeln!(out, "{}\n", numbers.join(" ")).unwrap();
+
+    for _ in 0..size.max(1) {
+        let from = loop {
+            let i = rng.below(stacks.len());
+            if !stacks[i].is_empty() {
+                break i;
+            }
+        };
+        let to = (from + 1 + rng.below(stacks.len() - 1)) % stacks.len();
+        let count = rng.range(1, stacks[from].len() as i64) as usize;
+        let keep = stacks[from].len() - count;
+        let moved = stacks[from].split_off(keep);
+        stacks[to].extend(moved);
+        writeln!(out, "move {count} from {} to {}", from + 1, to + 1).unwrap();
+    }
+    out
+}
+
+/// A datastream of `size` characters (at least 14), with 14 different ones in a row somewhere for
+/// the start-of-message marker, and so also a start-of-packet one.
+pub fn day6(rng: &mut Rng, size: usize) -> String {
+    let mut stream = word(rng, size.max(14)).into_bytes();
+    let mut letters = LOWER.to_vec();
+    rng.shuffle(&mut letters);
+    let at = rng.below(stream.len() - 13);
+    stream[at..at + 14].copy_from_slice(&letters[..14]);
+    format!("{}\n", String::from_utf8(stream).unwrap())
+}
+
+/// A terminal session exploring `size` files.
+pub fn day7(rng: &mut Rng, size: usize) -> String {
+    fn visit(rng: &mut Rng, files: usize, max_file: i64, out: &mut String) {
+        let here = files.min(rng.range(1, 4) as usize);
+        let left = files - here;
+        let dirs = if left == 0 { 0 } else { rng.range(1, 3) as usize };
+        let mut names = HashSet::new();
+        while names.len() < here + dirs {
+            let len = rng.range(1, 8) as usize;
+            names.insert(word(rng, len));
+        }
+        let mut names: Vec<String> = names.into_iter().collect();
+        names.sort();
+        rng.shuffle(&mut names);
+        let (dir_names, file_names) = names.split_at(dirs);
+
+        writeln!(out, "$ ls").unwrap();
+        for name in dir_names {
+            writeln!(out, "dir {name}").unwrap();
+        }
+        for name in file_names {
+            let ext = if rng.one_in(2) { format!(".{}", word(rng, 3)) } else { String::new() };
+            writeln!(out, "{} {name}{ext}", rng.range(1, max_file)).unwrap();
+        }
+        for (i, name) in dir_names.iter().enumerate() {
+            let share = if i + 1 == dirs { left - left / dirs * i } else { left / dirs };
+            writeln!(out, "$ cd {name}").unwrap();
+            visit(rng, share, max_file, out);
+            writeln!(out, "$ cd ..").unwrap();
+        }
+    }
+
+    let size = size.max(1);
+    // Keeps the total (and so every directory) within the puzzle's disk of 70000000.
+    let max_file = (60_000_000 / size as i64).clamp(1, 400_000);
+    let mut out = String::from("$ cd /\n");
+    visit(rng, size, max_file, &mut out);
+    out
+}
+
+/// A `size` by `size` forest.
+pub fn day8(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    (0..size)
+        .map(|_| {
+            (0..size).map(|_| char::from(b'0' + rng.range(0, 9) as u8)).collect::<String>() + "\n"
+        })
+        .collect()
+}
+
+/// `size` moves of the head.
+pub fn day9(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| format!("{} {}\n", rng.pick(&['U', 'D', 'L', 'R']), rng.range(1, 19)))
+        .collect()
+}
+
+/// `size` instructions, and as many `noop`s after them as make whole rows of 40 cycles.
+pub fn day10(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    let mut cycles = 0;
+    for _ in 0..size.max(1) {
+        if rng.one_in(3) {
+            out += "noop\n";
+            cycles += 1;
+        } else {
+            let n = rng.range(1, 20);
+            writeln!(out, "addx {}", if rng.one_in(2) { n } else { -n }).unwrap();
+            cycles += 2;
+        }
+    }
+    while cycles % 40 != 0 {
+        out += "noop\n";
+        cycles += 1;
+    }
+    out
+}
+
+/// `size` monkeys (at least two). The divisors are small primes, so that the worry levels of
+/// part 2 stay in range, and only one monkey squares them.
+pub fn day11(rng: &mut Rng, size: usize) -> String {
+    const PRIMES: [i64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+    let size = size.max(2);
+    let squarer = rng.below(size);
+    let monkeys: Vec<String> = (0..size)
+        .map(|i| {
+            let items: Vec<String> =
+                (0..rng.range(1, 6)).map(|_| rng.range(50, 99).to_string()).collect();
+            let op = if i == squarer {
+                String::from("* old")
+            } else if rng.one_in(2) {
+                format!("* {}", rng.range(2, 19))
+            } else {
+                format!("+ {}", rng.range(1, 8))
+            };
+            let other = |rng: &mut Rng| (i + 1 + rng.below(size - 1)) % size;
+            let if_true = other(rng);
+            let if_false = if size > 2 {
+                loop {
+                    let m = other(rng);
+                    if m != if_true {
+                        break m;
+                    }
+                }
+            } else {
+                if_true
+            };
+            format!(
+                "Monkey {i}:\n  Starting items: {}\n  Operation: new = old {op}\n  Test: divisible by {}\n    If true: throw to monkey {if_true}\n    If false: throw to monkey {if_false}\n",
+                items.join(", "),
+                PRIMES[i % PRIMES.len()]
+            )
+        })
+        .collect();
+    monkeys.join("\n")
+}
+
+/// A `size` by `size` height map (at least 14, to fit the climb from `a` to `z`), rising from
+/// `S` in the top left to `E` in the bottom right, with some pits.
+pub fn day12(rng: &mut Rng, size: usize) -> String {
+    let n = size.max(14);
+    let mut out = String::new();
+    for y in 0..n {
+        for x in 0..n {
+            let c = if (x, y) == (0, 0) {
+                'S'
+            } else if (x, y) == (n - 1, n - 1) {
+                'E'
+            } else if rng.one_in(12) {
+                'a'
+            } else {
+                char::from(b'a' + (25 * (x + y) / (2 * n - 2)) as u8)
+            };
+            out.push(c);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn packet(rng: &mut Rng, depth: usize) -> String {
+    let items: Vec<String> = (0..rng.range(0, 5))
+        .map(|_| {
+            if depth < 4 && rng.one_in(3) {
+                packet(rng, depth + 1)
+            } else {
+                rng.range(0, 10).to_string()
+            }
+        })
+        .collect();
+    format!("[{}]", items.join(","))
+}
+
+/// `size` pairs of packets.
+pub fn day13(rng: &mut Rng, size: usize) -> String {
+    let pairs: Vec<String> =
+        (0..size.max(1)).map(|_| format!("{}\n{}\n", packet(rng, 0), packet(rng, 0))).collect();
+    pairs.join("\n")
+}
+
+/// `size` paths of rock below the source of the sand.
+pub fn day14(rng: &mut Rng, size: usize) -> String {
+    let width = 20 + size as i64;
+    let depth = 10 + 2 * size as i64;
+    (0..size.max(1))
+        .map(|_| {
+            let mut x = rng.range((500 - width).max(0), 500 + width);
+            let mut y = rng.range(2, depth);
+            let mut points = vec![format!("{x},{y}")];
+            for i in 0..rng.range(1, 4) {
+                let step = rng.range(1, 8) * if rng.one_in(2) { 1 } else { -1 };
+                if i % 2 == 0 {
+                    x = (x + step).max(0);
+                } else {
+                    y = (y + step).max(1);
+                }
+                points.push(format!("{x},{y}"));
+            }
+            points.join(" -> ") + "\n"
+        })
+        .collect()
+}
+
+/// `size` sensors in the area searched by part 2.
+pub fn day15(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let (sx, sy) = (rng.range(0, 4_000_000), rng.range(0, 4_000_000));
+            let dist = rng.range(100_000, 1_000_000);
+            let dx = rng.range(-dist, dist);
+            let dy = (dist - dx.abs()) * if rng.one_in(2) { 1 } else { -1 };
+            format!("Sensor at x={sx}, y={sy}: closest beacon is at x={}, y={}\n", sx + dx, sy + dy)
+        })
+        .collect()
+}
+
+/// `AA` and `size` more valves (at most 675, for want of names), all connected, a third of them
+/// with a flow up to the 16 that the solution handles.
+pub fn day16(rng: &mut Rng, size: usize) -> String {
+    let mut names: Vec<String> = (b'A'..=b'Z')
+        .flat_map(|a| (b'A'..=b'Z').map(move |b| String::from_utf8(vec![a, b]).unwrap()))
+        .filter(|name| name != "AA")
+        .collect();
+    rng.shuffle(&mut names);
+    names.truncate(size.clamp(1, names.len()));
+    names.insert(0, String::from("AA"));
+
+    let mut tunnels = vec![Vec::new(); names.len()];
+    let mut connect = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for i in 1..names.len() {
+        connect(i, rng.below(i));
+    }
+    for _ in 0..names.len() / 5 {
+        connect(rng.below(names.len()), rng.below(names.len()));
+    }
+
+    let mut useful = 0;
+    let mut lines: Vec<String> = names
+        .iter()
+        .zip(&tunnels)
+        .enumerate()
+        .map(|(i, (name, to))| {
+            let flows = i > 0 && useful < 16 && rng.one_in(3);
+            useful += flows as usize;
+            let rate = if flows { rng.range(3, 25) } else { 0 };
+            let to: Vec<&str> = to.iter().map(|&j| names[j].as_str()).collect();
+            let tunnels = if to.len() == 1 {
+                format!("tunnel leads to valve {}", to[0])
+            } else {
+                format!("tunnels lead to valves {}", to.join(", "))
+            };
+            format!("Valve {name} has flow rate={rate}; {tunnels}\n")
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+    lines.concat()
+}
+
+/// A jet pattern of `size` jets.
+pub fn day17(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1)).map(|_| rng.pick(&['<', '>'])).collect::<String>() + "\n"
+}
+
+/// `size` distinct cubes, filling about half of the space they are in.
+pub fn day18(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let side = (2.0 * size as f64).cbrt().ceil() as i64 + 1;
+    let mut cubes = HashSet::new();
+    let mut out = String::new();
+    while cubes.len() < size {
+        let cube = (rng.range(1, side), rng.range(1, side), rng.range(1, side));
+        if cubes.insert(cube) {
+            writeln!(out, "{},{},{}", cube.0, cube.1, cube.2).unwrap();
+        }
+    }
+    out
+}
+
+/// `size` blueprints.
+pub fn day19(rng: &mut Rng, size: usize) -> String {
+    (1..=size.max(1))
+        .map(|i| {
+            format!(
+                "Blueprint {i}: Each ore robot costs {} ore. Each clay robot costs {} ore. Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian.\n",
+                rng.range(2, 4),
+                rng.range(2, 4),
+                rng.range(2, 4),
+                rng.range(5, 20),
+                rng.range(2, 4),
+                rng.range(5, 20)
+            )
+        })
+        .collect()
+}
+
+/// `size` numbers, exactly one of them 0.
+pub fn day20(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let zero = rng.below(size);
+    (0..size)
+        .map(|i| {
+            let n = if i == zero {
+                0
+            } else {
+                rng.range(1, 10000) * if rng.one_in(2) { 1 } else { -1 }
+            };
+            format!("{n}\n")
+        })
+        .collect()
+}
+
+/// About `size` monkeys (at most 200000, for want of names), in a tree below `root`. Both sides
+/// of `root` yell the same number with `humn` as it is, so part 2's answer is `humn`'s own
+/// number, and every division is exact.
+pub fn day21(rng: &mut Rng, size: usize) -> String {
+    struct Tree<'a> {
+        rng: &'a mut Rng,
+        names: HashSet<String>,
+        lines: Vec<String>,
+    }
+
+    impl Tree<'_> {
+        fn name(&mut self) -> String {
+            loop {
+                let name = word(self.rng, 4);
+                if name != "root" && name != "humn" && self.names.insert(name.clone()) {
+                    return name;
+                }
+            }
+        }
+
+        /// A monkey yelling `value`, with `budget` monkeys below it (itself included).
+        fn monkey(&mut self, value: i64, budget: usize, humn: bool) -> String {
+            let name = if humn && budget < 3 { String::from("humn") } else { self.name() };
+            if budget < 3 {
+                self.lines.push(format!("{name}: {value}"));
+                return name;
+            }
+            let factor = (2..=9).find(|f| value % f == 0 && value > *f);
+            let (a, op, b) = match self.rng.below(4) {
+                0 if value >= 2 => {
+                    let a = self.rng.range(1, value - 1);
+                    (a, '+', value - a)
+                }
+                1 if factor.is_some() => (value / factor.unwrap(), '*', factor.unwrap()),
+                2 if value < 1_000_000_000 => {
+                    let f = self.rng.range(2, 9);
+                    (value * f, '/', f)
+                }
+                _ => {
+                    let b = self.rng.range(1, 50);
+                    (value + b, '-', b)
+                }
+            };
+            let left = 1 + self.rng.below(budget - 2);
+            let humn_left = self.rng.one_in(2);
+            let a = self.monkey(a, left, humn && humn_left);
+            let b = self.monkey(b, budget - 1 - left, humn && !humn_left);
+            self.lines.push(format!("{name}: {a} {op} {b}"));
+            name
+        }
+    }
+
+    let size = size.clamp(3, 200_000);
+    let mut tree = Tree { rng, names: HashSet::new(), lines: Vec::new() };
+    let value = tree.rng.range(1, 1000);
+    let left = (size - 1) / 2;
+    let a = tree.monkey(value, left, true);
+    let b = tree.monkey(value, size - 1 - left, false);
+    tree.lines.push(format!("root: {a} + {b}"));
+    tree.rng.shuffle(&mut tree.lines);
+    tree.lines.iter().map(|line| format!("{line}\n")).collect()
+}
+
+/// A board laid out like the sample's cube with edges of 4 tiles, or from a `size` of 50 like the
+/// real input's with edges of 50 (the only cubes part 2 knows how to fold), and a path of
+/// `2 * size` moves.
+pub fn day22(rng: &mut Rng, size: usize) -> String {
+    const SAMPLE: &[&str] = &["  #", "###", "  ##"];
+    const INPUT: &[&str] = &[" ##", " # ", "## ", "#  "];
+    let (edge, net) = if size < 50 { (4, SAMPLE) } else { (50, INPUT) };
+    let mut out = String::new();
+    for (y, faces) in net.iter().enumerate() {
+        for row in 0..edge {
+            let line: String = faces
+                .trim_end()
+                .chars()
+                .flat_map(|face| (0..edge).map(move |col| (face, col)))
+                .map(|(face, col)| match face {
+                    ' ' => ' ',
+                    _ if y == 0 && row == 0 && col == 0 => '.',
+                    _ if rng.one_in(10) => '#',
+                    _ => '.',
+                })
+                .collect();
+            writeln!(out, "{line}").unwrap();
+        }
+    }
+    out.push('\n');
+    for i in 0..2 * size.max(1) {
+        if i > 0 {
+            out.push(rng.pick(&['L', 'R']));
+        }
+        write!(out, "{}", rng.range(1, 50)).unwrap();
+    }
+    out.push('\n');
+    out
+}
+
+/// A `size` by `size` grove, half of it taken by elves, and at least one.
+pub fn day23(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut grove: Vec<u8> = (0..size * size).map(|_| rng.pick(b"#.")).collect();
+    if !grove.contains(&b'#') {
+        let at = rng.below(grove.len());
+        grove[at] = b'#';
+    }
+    grove.chunks(size).map(|row| String::from_utf8(row.to_vec()).unwrap() + "\n").collect()
+}
+
+/// A basin of `size` by `size` tiles inside its walls, a third of them with a blizzard. None
+/// blow up or down the columns of the entrance and exit, as in the real input.
+pub fn day24(rng: &mut Rng, size: usize) -> String {
+    let n = size.max(2);
+    let mut out = format!("#.{}\n", "#".repeat(n));
+    for _ in 0..n {
+        out.push('#');
+        for x in 0..n {
+            let arrows: &[char] =
+                if x == 0 || x == n - 1 { &['<', '>'] } else { &['^', '>', 'v', '<'] };
+            out.push(if rng.one_in(3) { rng.pick(arrows) } else { '.' });
+        }
+        out.push_str("#\n");
+    }
+    out + &format!("{}.#\n", "#".repeat(n))
+}
+
+/// `size` fuel requirements.
+pub fn day25(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1)).map(|_| day25::encode(rng.range(1, 10_000_000_000)) + "\n").collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng_range() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            assert!((-3..=3).contains(&rng.range(-3, 3)));
+        }
+        let mut items = [1, 2, 3, 4, 5];
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_seeded() {
+        assert_eq!(day13(&mut Rng::new(1), 5), day13(&mut Rng::new(1), 5));
+        assert_ne!(day13(&mut Rng::new(1), 5), day13(&mut Rng::new(2), 5));
+    }
+}
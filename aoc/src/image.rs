@@ -0,0 +1,112 @@
+//! Saves pictures of the days' grids as PNG images, and their animations as GIFs.
+
+use aoc_common::animate::Animation;
+use aoc_common::picture::Picture;
+use aoc_common::{BBox2, Point2};
+use std::io::{self, Write};
+
+/// Writes `picture` as a PNG, each cell a `scale` by `scale` square.
+pub fn write_png<W: Write>(picture: &Picture, scale: usize, out: W) -> io::Result<()> {
+    let pixels = picture.scale(scale);
+    let mut encoder = png::Encoder::new(out, pixels.width() as u32, pixels.height() as u32);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(picture.palette.concat());
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    let data: Vec<u8> = pixels.rows().flatten().copied().collect();
+    writer.write_image_data(&data).map_err(io::Error::other)
+}
+
+/// The pictures of `animation`, every `every` steps from the first to the last.
+pub fn frames(animation: &mut dyn Animation, every: usize) -> Option<Vec<Picture>> {
+    let mut frames = vec![animation.picture()?];
+    let mut steps = 0;
+    while animation.step() {
+        steps += 1;
+        if steps % every == 0 {
+            frames.push(animation.picture()?);
+        }
+    }
+    if steps % every != 0 {
+        frames.push(animation.picture()?);
+    }
+    Some(frames)
+}
+
+/// Writes `frames` as an animated GIF, lined up by their origins, each cell a `scale` by `scale`
+/// square and each frame shown for `delay` hundredths of a second. Uncovered parts of a frame take
+/// the first colour of its palette.
+pub fn write_gif<W: Write>(frames: &[Picture], scale: usize, delay: u16, out: W) -> io::Result<()> {
+    let corners: Vec<Point2<i32>> = frames
+        .iter()
+        .flat_map(|f| {
+            let size = Point2 { x: f.cells.width() as i32, y: f.cells.height() as i32 };
+            [f.origin, f.origin + size - Point2 { x: 1, y: 1 }]
+        })
+        .collect();
+    let Some(bounds) = BBox2::from_points(&corners) else { return Ok(()) };
+    let size = |n: i32| {
+        u16::try_from(n as usize * scale)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Too large for a GIF"))
+    };
+    let (width, height) = (size(bounds.width())?, size(bounds.height())?);
+
+    let mut encoder = gif::Encoder::new(out, width, height, &[]).map_err(io::Error::other)?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(io::Error::other)?;
+    for picture in frames {
+        let offset = picture.origin - bounds.min;
+        let mut screen = vec![0; width as usize * height as usize];
+        let pixels = picture.scale(scale);
+        for (y, row) in pixels.rows().enumerate() {
+            let start =
+                (offset.y as usize * scale + y) * width as usize + offset.x as usize * scale;
+            screen[start..start + row.len()].copy_from_slice(row);
+        }
+        let mut frame = gif::Frame::from_indexed_pixels(width, height, screen, None);
+        frame.palette = Some(picture.palette.concat());
+        frame.delay = delay;
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn picture(origin: Point2<i32>, cells: Vec<Vec<u8>>) -> Picture {
+        Picture {
+            origin,
+            cells: aoc_common::Grid::from_rows(cells),
+            palette: vec![[0; 3], [255; 3]],
+        }
+    }
+
+    #[test]
+    fn test_png() {
+        let mut out = Vec::new();
+        write_png(&picture(Point2::origin(), vec![vec![0, 1], vec![1, 0]]), 3, &mut out).unwrap();
+        assert!(out.starts_with(b"\x89PNG"));
+        let decoder = png::Decoder::new(out.as_slice());
+        let reader = decoder.read_info().unwrap();
+        assert_eq!((reader.info().width, reader.info().height), (6, 6));
+    }
+
+    #[test]
+    fn test_gif() {
+        let frames = [
+            picture(Point2::origin(), vec![vec![1]]),
+            picture(Point2 { x: -1, y: 0 }, vec![vec![1, 1], vec![0, 1]]),
+        ];
+        let mut out = Vec::new();
+        write_gif(&frames, 2, 10, &mut out).unwrap();
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = options.read_info(out.as_slice()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (4, 4));
+        let first = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!(&first.buffer[..], &[0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert!(decoder.read_next_frame().unwrap().is_some());
+        assert!(decoder.read_next_frame().unwrap().is_none());
+    }
+}
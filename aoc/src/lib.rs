@@ -0,0 +1,280 @@
+//! Runs the solutions of all days through a single entry point.
+
+pub mod animate;
+pub mod answers;
+pub mod bench;
+pub mod gen;
+#[cfg(feature = "images")]
+pub mod image;
+pub mod memory;
+pub mod output;
+pub mod summary;
+
+use answers::Manifest;
+use aoc_common::animate::Animation;
+use aoc_common::params::Params;
+use aoc_common::picture::Picture;
+use aoc_common::verbose::span;
+use aoc_common::{ParseError, Violation};
+use bench::Bencher;
+use gen::Rng;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Puzzle input for a day.
+pub struct Input {
+    pub text: String,
+    /// Whether this is the sample from the puzzle text, which some days solve with different
+    /// parameters.
+    pub sample: bool,
+    /// Options changing the day's puzzle parameters, as name and value pairs, which must have
+    /// passed `Day::check_params`.
+    pub params: Vec<(String, String)>,
+}
+
+impl Input {
+    /// The puzzle parameters for this input.
+    pub fn params<P: Params>(&self) -> P {
+        P::from_options(&self.params, self.sample).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+pub type Part = fn(&Input) -> Result<String, ParseError>;
+
+type Animate = fn(&Input) -> Result<Box<dyn Animation>, ParseError>;
+
+type Render = fn(&Input) -> Result<Picture, ParseError>;
+
+type Validate = fn(&Input) -> Result<Result<(), Violation>, ParseError>;
+
+type CheckParams = fn(&[(String, String)]) -> Result<(), String>;
+
+pub struct Day {
+    pub name: &'static str,
+    pub part1: Part,
+    pub part2: Option<Part>,
+    /// Times the parse step and each part separately.
+    pub bench: fn(&Input, &mut Bencher) -> Result<(), ParseError>,
+    /// Generates a random input of the given size.
+    pub gen: fn(&mut Rng, usize) -> String,
+    /// Sets up the day's simulation for watching, for the days that have one.
+    pub animate: Option<Animate>,
+    /// Draws the day's grid, for the days that have colours for it.
+    pub picture: Option<Render>,
+    /// Checks what the day's solution assumes about its input beyond the format, for the days
+    /// that assume anything more.
+    pub validate: Option<Validate>,
+    /// Checks options for the day's puzzle parameters.
+    pub check_params: CheckParams,
+    /// The options of the day's puzzle parameters, a line each.
+    pub params_usage: fn() -> String,
+}
+
+impl Day {
+    /// Path to one of the input files kept with the day, e.g. `test` or `input`.
+    pub fn input_path(&self, kind: &str) -> PathBuf {
+        [env!("CARGO_MANIFEST_DIR"), "..", self.name, &format!("{kind}.txt")].iter().collect()
+    }
+
+    /// Reads `test`, `input`, stdin for `-` or the file at any other path, which can be compressed
+    /// (see `aoc_common::input`).
+    pub fn load_input(&self, input: &str) -> io::Result<Input> {
+        let (path, sample) = match input {
+            "test" => (self.input_path("test"), true),
+            "input" => (self.input_path("input"), false),
+            path => (path.into(), false),
+        };
+        let text = aoc_common::input::read(&path.to_string_lossy())?;
+        Ok(Input { text, sample, params: Vec::new() })
+    }
+
+    /// Path to the day's answers manifest.
+    pub fn manifest_path(&self) -> PathBuf {
+        [env!("CARGO_MANIFEST_DIR"), "..", self.name, "answers.txt"].iter().collect()
+    }
+
+    /// The recorded answers, empty if none were recorded yet.
+    pub fn manifest(&self) -> io::Result<Manifest> {
+        let text = match fs::read_to_string(self.manifest_path()) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+        Manifest::parse(&text).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {e}", self.manifest_path().display()),
+            )
+        })
+    }
+
+    pub fn parts(&self) -> impl Iterator<Item = (u8, Part)> {
+        [Some((1, self.part1)), self.part2.map(|p| (2, p))].into_iter().flatten()
+    }
+}
+
+/// Runs `f` in a span named after the day and what `f` does, e.g. `day16 part 2`, so that `-v`
+/// shows how long it takes and what it reports meanwhile.
+fn traced<T>(day: &str, stage: &str, f: impl FnOnce() -> T) -> T {
+    let _span = span(format_args!("{day} {stage}"));
+    f()
+}
+
+fn check_params<P: Params>(options: &[(String, String)]) -> Result<(), String> {
+    P::from_options(options, false).map(|_| ())
+}
+
+/// What a part returns, as the runner prints and records it.
+pub trait Answer {
+    fn answer(&self) -> String;
+}
+
+macro_rules! display_answer {
+    ($($ty:ty),*) => {
+        $(impl Answer for $ty {
+            fn answer(&self) -> String { self.to_string() }
+        })*
+    };
+}
+
+display_answer!(i32, i64, u32, u64, usize, String);
+
+/// The answer of a part that may find none with other parameters than the puzzle's, like day 15's
+/// search of a smaller area.
+impl<T: Answer> Answer for Option<T> {
+    fn answer(&self) -> String { self.as_ref().map_or_else(|| String::from("none"), T::answer) }
+}
+
+/// The `Day` of a day crate, given the type of its puzzle parameters if it has any, e.g.
+/// `day!(day7, Params)`.
+macro_rules! day {
+    ($day:ident $(, $params:ident)?) => {
+        Day {
+            name: stringify!($day),
+            part1: |input| {
+                let parsed = traced(stringify!($day), "parse", || $day::parse(&input.text))?;
+                let answer = traced(stringify!($day), "part 1", || {
+                    $day::part1(&parsed $(, &input.params::<$day::$params>())?)
+                });
+                Ok(answer.answer())
+            },
+            part2: Some(|input| {
+                let parsed = traced(stringify!($day), "parse", || $day::parse(&input.text))?;
+                let answer = traced(stringify!($day), "part 2", || {
+                    $day::part2(&parsed $(, &input.params::<$day::$params>())?)
+                });
+                Ok(answer.answer())
+            }),
+            bench: |input, b| {
+                let parsed = $day::parse(&input.text)?;
+                $(let params = input.params::<$day::$params>();)?
+                b.run("parse", || $day::parse(&input.text));
+                b.run("part1", || $day::part1(&parsed $(, &params as &$day::$params)?));
+                b.run("part2", || $day::part2(&parsed $(, &params as &$day::$params)?));
+                Ok(())
+            },
+            gen: gen::$day,
+            animate: None,
+            picture: None,
+            validate: None,
+            check_params: check_params::<day!(@params $day $(, $params)?)>,
+            params_usage: <day!(@params $day $(, $params)?) as Params>::usage,
+        }
+    };
+    (@params $day:ident) => { () };
+    (@params $day:ident, $params:ident) => { $day::$params };
+}
+
+/// The `animate` of a day with an `animation` function, given the type of its puzzle parameters if
+/// the animation takes them.
+macro_rules! animate {
+    ($day:ident $(, $params:ident)?) => {
+        Some(|input| {
+            let parsed = $day::parse(&input.text)?;
+            Ok(Box::new($day::animation(&parsed $(, &input.params::<$day::$params>())?)))
+        })
+    };
+}
+
+/// The `picture` of a day with a `picture` function.
+macro_rules! picture {
+    ($day:ident) => {
+        Some(|input| Ok($day::picture(&$day::parse(&input.text)?)))
+    };
+}
+
+/// The `validate` of a day with a `validate` function, given the type of its puzzle parameters if
+/// the validation takes them.
+macro_rules! validate {
+    ($day:ident $(, $params:ident)?) => {
+        Some(|input| {
+            let parsed = $day::parse(&input.text)?;
+            Ok($day::validate(&parsed $(, &input.params::<$day::$params>())?))
+        })
+    };
+}
+
+pub static DAYS: [Day; 25] = [
+    day!(day1, Params),
+    Day { validate: validate!(day2, Params), ..day!(day2, Params) },
+    Day { validate: validate!(day3, Params), ..day!(day3, Params) },
+    Day { validate: validate!(day4), ..day!(day4) },
+    Day { validate: validate!(day5), ..day!(day5) },
+    Day { validate: validate!(day6, Params), ..day!(day6, Params) },
+    Day { validate: validate!(day7, Params), ..day!(day7, Params) },
+    Day { picture: picture!(day8), ..day!(day8) },
+    Day {
+        animate: animate!(day9, Params),
+        validate: validate!(day9, Params),
+        ..day!(day9, Params)
+    },
+    Day { validate: validate!(day10, Params), ..day!(day10, Params) },
+    Day { validate: validate!(day11), ..day!(day11, Params) },
+    Day { picture: picture!(day12), validate: validate!(day12), ..day!(day12) },
+    day!(day13),
+    Day {
+        animate: animate!(day14),
+        picture: picture!(day14),
+        validate: validate!(day14),
+        ..day!(day14)
+    },
+    day!(day15, Params),
+    Day { validate: validate!(day16, Params), ..day!(day16, Params) },
+    Day { animate: animate!(day17, Params), ..day!(day17, Params) },
+    day!(day18),
+    day!(day19, Params),
+    day!(day20, Params),
+    Day { validate: validate!(day21), ..day!(day21) },
+    Day { picture: picture!(day22), validate: validate!(day22), ..day!(day22) },
+    Day {
+        animate: animate!(day23),
+        picture: picture!(day23),
+        validate: validate!(day23),
+        ..day!(day23, Params)
+    },
+    Day { animate: animate!(day24, Params), validate: validate!(day24), ..day!(day24, Params) },
+    Day {
+        name: "day25",
+        part1: |input| {
+            let fuel = traced("day25", "parse", || day25::parse(&input.text))?;
+            Ok(traced("day25", "part 1", || day25::part1(&fuel)))
+        },
+        part2: None,
+        bench: |input, b| {
+            let fuel = day25::parse(&input.text)?;
+            b.run("parse", || day25::parse(&input.text));
+            b.run("part1", || day25::part1(&fuel));
+            Ok(())
+        },
+        gen: gen::day25,
+        animate: None,
+        picture: None,
+        validate: None,
+        check_params: check_params::<()>,
+        params_usage: <() as Params>::usage,
+    },
+];
+
+/// Looks up a day by name, e.g. `day7`.
+pub fn find(name: &str) -> Option<&'static Day> { DAYS.iter().find(|day| day.name == name) }
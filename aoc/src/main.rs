@@ -0,0 +1,405 @@
+use aoc::animate::{play, stdin_controls};
+use aoc::gen::Rng;
+use aoc::memory::measure;
+use aoc::output::{Format, Record};
+use aoc::summary::{run_all, table, Task};
+use aoc::{Day, Input, DAYS};
+use aoc_common::verbose::set_verbose;
+use std::env::args;
+use std::fs;
+use std::io;
+use std::process::exit;
+use std::thread;
+use std::time::Instant;
+
+const USAGE: &str = "Usage: aoc run <dayN|all> [--input test|input|PATH] [--part 1|2] [OPTIONS]
+       aoc run <dayN> [--input test|input|PATH] [--part 1|2] [--<param> VALUE...] [OPTIONS]
+       aoc record <dayN|all> [--input test|input] [--part 1|2] [OPTIONS]
+       aoc summary <dayN|all> [--input test|input|PATH] [--jobs N]
+       aoc validate <dayN|all> [--input test|input|PATH]
+       aoc gen <dayN> [--size N] [--seed N]
+       aoc run <day9|day14|day17|day23|day24> --animate [--fps N] [--input test|input|PATH]
+       aoc image <day8|day12|day14|day22|day23> [--output PATH.png|PATH.gif] [--scale N]
+                 [--every N] [--fps N] [--input test|input|PATH]
+Options: --format text|json|tsv  How to print the answers (default text)
+         -v, --verbose           Show the days' progress and diagnostics on stderr
+Parameters: the puzzle's sizes and counts of a day, e.g. --rounds 20, listed on a bad one
+Animation: Enter pauses, and steps while paused; c continues, +/- change the speed, q quits";
+
+#[derive(PartialEq)]
+enum Command {
+    Run,
+    /// Runs the days and stores their answers in the manifests.
+    Record,
+    /// Runs the days' parts in parallel and prints a table of them, checked against the manifests.
+    Summary,
+    /// Checks what the days' solutions assume about their inputs, without solving them.
+    Validate,
+    /// Prints a random input for a day.
+    Gen,
+    /// Saves a picture of a day's grid, or a GIF of its animation.
+    Image,
+}
+
+struct Options {
+    command: Command,
+    days: Vec<&'static Day>,
+    input: String,
+    part: Option<u8>,
+    format: Format,
+    verbose: bool,
+    animate: bool,
+    fps: f64,
+    size: usize,
+    seed: u64,
+    output: Option<String>,
+    scale: usize,
+    every: usize,
+    jobs: usize,
+    /// Options for the day's puzzle parameters, as name and value pairs.
+    params: Vec<(String, String)>,
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("{msg}");
+    eprintln!("{USAGE}");
+    exit(2)
+}
+
+fn parse_args(args: &[String]) -> Options {
+    let mut args = args.iter();
+    let command = match args.next().map(|s| s.as_str()) {
+        Some("run") => Command::Run,
+        Some("record") => Command::Record,
+        Some("summary") => Command::Summary,
+        Some("validate") => Command::Validate,
+        Some("gen") => Command::Gen,
+        Some("image") => Command::Image,
+        _ => fail("Expected a command"),
+    };
+    let days = match args.next().map(|s| s.as_str()) {
+        Some("all") => DAYS.iter().collect(),
+        Some(name) => match aoc::find(name) {
+            Some(day) => vec![day],
+            None => fail(&format!("Unknown day {name}")),
+        },
+        None => fail("Expected a day"),
+    };
+
+    let mut options = Options {
+        command,
+        days,
+        input: String::from("input"),
+        part: None,
+        format: Format::Text,
+        verbose: false,
+        animate: false,
+        fps: 10.0,
+        size: 100,
+        seed: 0,
+        output: None,
+        scale: 4,
+        every: 1,
+        jobs: thread::available_parallelism().map_or(1, |n| n.get()),
+        params: Vec::new(),
+    };
+    while let Some(arg) = args.next() {
+        if arg == "-v" || arg == "--verbose" {
+            options.verbose = true;
+            continue;
+        }
+        if arg == "--animate" {
+            options.animate = true;
+            continue;
+        }
+        let Some(value) = args.next() else { fail(&format!("Missing value for {arg}")) };
+        match arg.as_str() {
+            "--input" => options.input = value.clone(),
+            "--part" => match value.as_str() {
+                "1" => options.part = Some(1),
+                "2" => options.part = Some(2),
+                _ => fail(&format!("Invalid part {value}")),
+            },
+            "--format" => options.format = value.parse().unwrap_or_else(|e: String| fail(&e)),
+            // Only `gen` takes these, so that a day's own parameters can have the same names.
+            "--size" if options.command == Command::Gen => {
+                options.size =
+                    value.parse().unwrap_or_else(|_| fail(&format!("Invalid size {value}")))
+            }
+            "--fps" => match value.parse() {
+                Ok(fps) if fps > 0.0 => options.fps = fps,
+                _ => fail(&format!("Invalid fps {value}")),
+            },
+            "--output" => options.output = Some(value.clone()),
+            "--scale" => match value.parse() {
+                Ok(scale) if scale > 0 => options.scale = scale,
+                _ => fail(&format!("Invalid scale {value}")),
+            },
+            "--every" => match value.parse() {
+                Ok(every) if every > 0 => options.every = every,
+                _ => fail(&format!("Invalid every {value}")),
+            },
+            "--jobs" => match value.parse() {
+                Ok(jobs) if jobs > 0 => options.jobs = jobs,
+                _ => fail(&format!("Invalid jobs {value}")),
+            },
+            "--seed" if options.command == Command::Gen => {
+                options.seed =
+                    value.parse().unwrap_or_else(|_| fail(&format!("Invalid seed {value}")))
+            }
+            _ => match arg.strip_prefix("--") {
+                Some(name) => options.params.push((name.to_string(), value.clone())),
+                None => fail(&format!("Unknown option {arg}")),
+            },
+        }
+    }
+    let named_input = ["test", "input"].contains(&options.input.as_str());
+    if options.days.len() > 1 && !named_input {
+        fail("An input file can only be given for a single day");
+    }
+    if options.command == Command::Gen && options.days.len() > 1 {
+        fail("Inputs can only be generated for a single day");
+    }
+    if options.command == Command::Image && options.days.len() > 1 {
+        fail("Images can only be made for a single day");
+    }
+    if options.animate && (options.command != Command::Run || options.days.len() > 1) {
+        fail("Only a single day can be animated, with run");
+    }
+    if options.animate && options.days[0].animate.is_none() {
+        fail(&format!("{} has no animation", options.days[0].name));
+    }
+    if options.command == Command::Summary && options.format != Format::Text {
+        fail("The summary is only printed as a table");
+    }
+    if options.command == Command::Record && !named_input {
+        fail("Only answers for test or input can be recorded");
+    }
+    if !options.params.is_empty() {
+        check_params(&options);
+    }
+    options
+}
+
+/// Checks the options for the puzzle parameters, which are only taken by a single day and not
+/// recorded, since the manifests hold the answers of the puzzle.
+fn check_params(options: &Options) {
+    if options.days.len() > 1 {
+        fail(&format!("Unknown option --{}", options.params[0].0));
+    }
+    if matches!(options.command, Command::Record | Command::Gen) {
+        fail("Puzzle parameters can only be changed with run, summary, validate and image");
+    }
+    let day = options.days[0];
+    if let Err(e) = (day.check_params)(&options.params) {
+        eprintln!("{e}");
+        let usage = (day.params_usage)();
+        if usage.is_empty() {
+            eprintln!("{} has no parameters", day.name);
+        } else {
+            eprint!("Parameters of {}:\n{usage}", day.name);
+        }
+        exit(2)
+    }
+}
+
+fn load_input(day: &Day, options: &Options) -> Input {
+    let mut input = day.load_input(&options.input).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        exit(1)
+    });
+    input.params = options.params.clone();
+    input
+}
+
+/// Whether `input` holds to what the day's solution assumes, printing what it breaks if not.
+fn valid(day: &Day, input: &Input) -> bool {
+    let Some(validate) = day.validate else { return true };
+    match validate(input) {
+        Ok(Ok(())) => true,
+        Ok(Err(violation)) => {
+            eprintln!("{violation}");
+            false
+        }
+        Err(e) => {
+            eprintln!("{}", e.report(&input.text));
+            false
+        }
+    }
+}
+
+/// Checks each day's input, and exits with 1 if any breaks what its solution assumes.
+fn validate(options: &Options) {
+    let mut failed = 0;
+    for &day in &options.days {
+        let input = load_input(day, options);
+        if !valid(day, &input) {
+            failed += 1;
+        } else if day.validate.is_some() {
+            println!("{}: ok", day.name);
+        } else {
+            println!("{}: no assumptions to check", day.name);
+        }
+    }
+    if failed > 0 {
+        exit(1)
+    }
+}
+
+fn animate(day: &Day, options: &Options) {
+    let input = load_input(day, options);
+    let mut animation = (day.animate.unwrap())(&input).unwrap_or_else(|e| {
+        eprintln!("{}", e.report(&input.text));
+        exit(1)
+    });
+    if let Err(e) = play(animation.as_mut(), options.fps, &stdin_controls(), &mut io::stdout()) {
+        eprintln!("{e}");
+        exit(1)
+    }
+}
+
+/// Prints the summary table, and exits with 1 if any part failed or gave another answer than the
+/// recorded one. Answers are only checked for `test` and `input` with the puzzle parameters, which
+/// is what the manifests hold.
+fn summary(options: &Options) {
+    let inputs: Vec<Input> = options.days.iter().map(|day| load_input(day, options)).collect();
+    let named_input = ["test", "input"].contains(&options.input.as_str());
+    let mut tasks = Vec::new();
+    for (day, input) in options.days.iter().zip(&inputs) {
+        let manifest = if named_input && options.params.is_empty() {
+            Some(day.manifest().unwrap_or_else(|e| {
+                eprintln!("{e}");
+                exit(1)
+            }))
+        } else {
+            None
+        };
+        for (part, solve) in day.parts() {
+            if options.part.is_some_and(|p| p != part) {
+                continue;
+            }
+            let expected = manifest.as_ref().and_then(|m| m.get(&options.input, part));
+            let expected = expected.map(str::to_string);
+            tasks.push(Task { day: day.name, part, solve, input, expected });
+        }
+    }
+
+    let start = Instant::now();
+    let runs = run_all(&tasks, options.jobs);
+    let elapsed = start.elapsed();
+    print!("{}", table(&runs));
+    let failed = runs.iter().filter(|run| !run.passed()).count();
+    let threads = options.jobs.min(tasks.len());
+    println!(
+        "\n{} parts in {elapsed:.2?} on {threads} thread{}, {failed} failed",
+        runs.len(),
+        if threads == 1 { "" } else { "s" }
+    );
+    if failed > 0 {
+        exit(1)
+    }
+}
+
+#[cfg(feature = "images")]
+fn image(day: &Day, options: &Options) {
+    use aoc::image::{frames, write_gif, write_png};
+    use std::fs::File;
+    use std::io::BufWriter;
+
+    let output = options.output.clone().unwrap_or_else(|| format!("{}.png", day.name));
+    let input = load_input(day, options);
+    let report = |e: aoc_common::ParseError| -> ! {
+        eprintln!("{}", e.report(&input.text));
+        exit(1)
+    };
+    let create = || File::create(&output).map(BufWriter::new);
+    let written = if output.ends_with(".gif") {
+        let Some(animate) = day.animate else { fail(&format!("{} has no animation", day.name)) };
+        let mut animation = animate(&input).unwrap_or_else(|e| report(e));
+        let Some(frames) = frames(animation.as_mut(), options.every) else {
+            fail(&format!("{} has no pictures for its animation", day.name))
+        };
+        let delay = (100.0 / options.fps).round() as u16;
+        create().and_then(|file| write_gif(&frames, options.scale, delay, file))
+    } else {
+        let Some(picture) = day.picture else { fail(&format!("{} has no picture", day.name)) };
+        let picture = picture(&input).unwrap_or_else(|e| report(e));
+        create().and_then(|file| write_png(&picture, options.scale, file))
+    };
+    if let Err(e) = written {
+        eprintln!("Can't write {output}: {e}");
+        exit(1)
+    }
+}
+
+#[cfg(not(feature = "images"))]
+fn image(_: &Day, _: &Options) {
+    fail("Images need the images feature: cargo run -r -p aoc --features images -- image ...")
+}
+
+fn main() {
+    let options = parse_args(&args().skip(1).collect::<Vec<_>>());
+    set_verbose(options.verbose);
+    if options.command == Command::Gen {
+        print!("{}", (options.days[0].gen)(&mut Rng::new(options.seed), options.size));
+        return;
+    }
+    if options.animate {
+        animate(options.days[0], &options);
+        return;
+    }
+    if options.command == Command::Image {
+        image(options.days[0], &options);
+        return;
+    }
+    if options.command == Command::Summary {
+        summary(&options);
+        return;
+    }
+    if options.command == Command::Validate {
+        validate(&options);
+        return;
+    }
+    if let Some(header) = options.format.header(aoc::memory::ENABLED) {
+        println!("{header}");
+    }
+
+    for &day in &options.days {
+        let input = load_input(day, &options);
+        if !valid(day, &input) {
+            exit(1)
+        }
+        let mut manifest = match options.command {
+            Command::Record => Some(day.manifest().unwrap_or_else(|e| {
+                eprintln!("{e}");
+                exit(1)
+            })),
+            Command::Run | Command::Summary | Command::Validate | Command::Gen | Command::Image => {
+                None
+            }
+        };
+        for (part, solve) in day.parts() {
+            if options.part.is_some_and(|p| p != part) {
+                continue;
+            }
+            let start = Instant::now();
+            let (answer, memory) = measure(|| solve(&input));
+            let elapsed = start.elapsed();
+            let answer = answer.unwrap_or_else(|e| {
+                eprintln!("{}", e.report(&input.text));
+                exit(1)
+            });
+            let record = Record { day: day.name, part, answer: &answer, elapsed, memory };
+            println!("{}", options.format.render(&record));
+            if let Some(manifest) = &mut manifest {
+                manifest.insert(&options.input, part, &answer);
+            }
+        }
+        if let Some(manifest) = manifest {
+            if let Err(e) = fs::write(day.manifest_path(), manifest.to_string()) {
+                eprintln!("Can't write {}: {e}", day.manifest_path().display());
+                exit(1)
+            }
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! How the runner prints answers: for people (`text`) or for scripts (`json`, `tsv`).
+//!
+//! Every answer is one record of day, part, answer and elapsed time. `json` prints one object
+//! per line, e.g. `{"day":"day1","part":1,"answer":"24000","elapsed":0.000012}`, with the time in
+//! seconds. `tsv` starts with a header line and escapes tabs, newlines and backslashes in answers.
+//!
+//! When allocations are counted (see `memory`), records also have the number of allocations, the
+//! bytes allocated and the peak memory, as `allocations`, `bytes` and `peak` fields and columns.
+
+use crate::memory::{Size, Usage};
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Tsv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "tsv" => Ok(Format::Tsv),
+            _ => Err(format!("Invalid format {s}")),
+        }
+    }
+}
+
+/// The answer to one part of a day.
+#[derive(Debug, Clone)]
+pub struct Record<'a> {
+    pub day: &'a str,
+    pub part: u8,
+    pub answer: &'a str,
+    pub elapsed: Duration,
+    pub memory: Option<Usage>,
+}
+
+impl Format {
+    /// What to print before the first record, if anything, given whether the records have their
+    /// memory usage.
+    pub fn header(self, memory: bool) -> Option<&'static str> {
+        match self {
+            Format::Tsv if memory => Some("day\tpart\tanswer\telapsed\tallocations\tbytes\tpeak"),
+            Format::Tsv => Some("day\tpart\tanswer\telapsed"),
+            Format::Text | Format::Json => None,
+        }
+    }
+
+    pub fn render(self, record: &Record) -> String {
+        let Record { day, part, answer, elapsed, memory } = record;
+        match self {
+            Format::Text => {
+                let mut stats = format!("{elapsed:.2?}");
+                if let Some(Usage { allocations, bytes, peak }) = memory {
+                    write!(
+                        stats,
+                        ", {allocations} allocations, {} allocated, {} peak",
+                        Size(*bytes),
+                        Size(*peak)
+                    )
+                    .unwrap();
+                }
+                if answer.contains('\n') {
+                    format!("{day} part {part} ({stats}):\n{}", answer.trim_end())
+                } else {
+                    format!("{day} part {part}: {answer} ({stats})")
+                }
+            }
+            Format::Json => {
+                let mut json = format!(
+                    r#"{{"day":{},"part":{part},"answer":{},"elapsed":{}"#,
+                    json_string(day),
+                    json_string(answer),
+                    elapsed.as_secs_f64()
+                );
+                if let Some(Usage { allocations, bytes, peak }) = memory {
+                    write!(json, r#","allocations":{allocations},"bytes":{bytes},"peak":{peak}"#)
+                        .unwrap();
+                }
+                json + "}"
+            }
+            Format::Tsv => {
+                let mut tsv =
+                    format!("{day}\t{part}\t{}\t{}", tsv_field(answer), elapsed.as_secs_f64());
+                if let Some(Usage { allocations, bytes, peak }) = memory {
+                    write!(tsv, "\t{allocations}\t{bytes}\t{peak}").unwrap();
+                }
+                tsv
+            }
+        }
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn tsv_field(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(answer: &str) -> Record<'_> {
+        Record { day: "day10", part: 2, answer, elapsed: Duration::from_millis(1500), memory: None }
+    }
+
+    fn with_memory(answer: &str) -> Record<'_> {
+        let memory = Usage { allocations: 3, bytes: 4096, peak: 1024 };
+        Record { memory: Some(memory), ..record(answer) }
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(Format::Text.render(&record("42")), "day10 part 2: 42 (1.50s)");
+        assert_eq!(Format::Text.render(&record("#.\n.#\n")), "day10 part 2 (1.50s):\n#.\n.#");
+        assert_eq!(
+            Format::Text.render(&with_memory("42")),
+            "day10 part 2: 42 (1.50s, 3 allocations, 4.0 KiB allocated, 1.0 KiB peak)"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            Format::Json.render(&record("#\"\\\n")),
+            r##"{"day":"day10","part":2,"answer":"#\"\\\n","elapsed":1.5}"##
+        );
+        assert_eq!(
+            Format::Json.render(&with_memory("42")),
+            concat!(
+                r#"{"day":"day10","part":2,"answer":"42","elapsed":1.5,"#,
+                r#""allocations":3,"bytes":4096,"peak":1024}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_tsv() {
+        assert_eq!(Format::Tsv.render(&record("#.\n.#\n")), "day10\t2\t#.\\n.#\\n\t1.5");
+        assert_eq!(Format::Tsv.render(&with_memory("42")), "day10\t2\t42\t1.5\t3\t4096\t1024");
+        assert_eq!(Format::Tsv.header(true).unwrap().split('\t').count(), 7);
+        assert_eq!("tsv".parse(), Ok(Format::Tsv));
+        assert!("xml".parse::<Format>().is_err());
+    }
+}
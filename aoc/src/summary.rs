@@ -0,0 +1,242 @@
+//! Runs many days at once on a pool of threads, each part on its own, and sums them up in a table
+//! checked against the recorded answers.
+
+use crate::answers::normalize;
+use crate::{Input, Part};
+use std::any::Any;
+use std::fmt::Write;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// One part of a day to run.
+pub struct Task<'a> {
+    pub day: &'static str,
+    pub part: u8,
+    pub solve: Part,
+    pub input: &'a Input,
+    /// The recorded answer, if it is to be checked.
+    pub expected: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Answer(String),
+    /// The input didn't parse, with the report of the error.
+    Invalid(String),
+    /// The part panicked, with the panic message.
+    Panic(String),
+}
+
+/// How a task went.
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub day: &'static str,
+    pub part: u8,
+    pub outcome: Outcome,
+    pub elapsed: Duration,
+    pub expected: Option<String>,
+}
+
+impl Run {
+    /// Whether the part gave an answer, the recorded one if there is any.
+    pub fn passed(&self) -> bool {
+        match &self.outcome {
+            Outcome::Answer(answer) => self.expected.as_ref().is_none_or(|e| *e == *answer),
+            Outcome::Invalid(_) | Outcome::Panic(_) => false,
+        }
+    }
+
+    /// What went wrong, for a run that didn't pass.
+    pub fn failure(&self) -> Option<String> {
+        let Run { day, part, .. } = self;
+        match &self.outcome {
+            _ if self.passed() => None,
+            Outcome::Answer(answer) => Some(format!(
+                "{day} part {part}: expected {:?}, got {answer:?}",
+                self.expected.as_deref().unwrap_or_default()
+            )),
+            Outcome::Invalid(report) => Some(format!("{day} part {part}: invalid input\n{report}")),
+            Outcome::Panic(message) => Some(format!("{day} part {part}: panicked: {message}")),
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(message), _) => message.to_string(),
+        (_, Some(message)) => message.clone(),
+        _ => String::from("unknown panic"),
+    }
+}
+
+fn run(task: &Task) -> Run {
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| (task.solve)(task.input)));
+    let elapsed = start.elapsed();
+    let outcome = match result {
+        Ok(Ok(answer)) => Outcome::Answer(normalize(&answer)),
+        Ok(Err(e)) => Outcome::Invalid(e.report(&task.input.text)),
+        Err(payload) => Outcome::Panic(panic_message(payload.as_ref())),
+    };
+    let expected = task.expected.clone();
+    Run { day: task.day, part: task.part, outcome, elapsed, expected }
+}
+
+/// Runs `tasks` on `threads` threads, taking the next task as soon as a thread is free. The runs
+/// come back in the order of the tasks.
+pub fn run_all(tasks: &[Task], threads: usize) -> Vec<Run> {
+    let next = AtomicUsize::new(0);
+    let runs = Mutex::new(Vec::with_capacity(tasks.len()));
+    thread::scope(|s| {
+        for _ in 0..threads.clamp(1, tasks.len().max(1)) {
+            s.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(task) = tasks.get(i) else { break };
+                let run = run(task);
+                runs.lock().unwrap().push((i, run));
+            });
+        }
+    });
+    let mut runs = runs.into_inner().unwrap();
+    runs.sort_by_key(|(i, _)| *i);
+    runs.into_iter().map(|(_, run)| run).collect()
+}
+
+fn cell(run: Option<&Run>) -> (String, String) {
+    let Some(run) = run else { return (String::from("-"), String::from("-")) };
+    let answer = match &run.outcome {
+        Outcome::Answer(answer) if answer.contains('\n') => {
+            format!("[{} lines]", answer.lines().count())
+        }
+        Outcome::Answer(answer) => answer.clone(),
+        Outcome::Invalid(_) => String::from("invalid input"),
+        Outcome::Panic(_) => String::from("panicked"),
+    };
+    let mark = match () {
+        _ if !run.passed() => " ✗",
+        _ if run.expected.is_some() => " ✓",
+        _ => "",
+    };
+    (answer + mark, format!("{:.2?}", run.elapsed))
+}
+
+/// A row per day with the answers and times of both parts, a ✓ next to the answers that match the
+/// recorded ones and a ✗ next to the failed ones, followed by what went wrong with each.
+pub fn table(runs: &[Run]) -> String {
+    let mut rows = vec![[
+        String::from("day"),
+        String::from("part 1"),
+        String::from("time"),
+        String::from("part 2"),
+        String::from("time"),
+    ]];
+    let mut days: Vec<&str> = runs.iter().map(|r| r.day).collect();
+    days.dedup();
+    for day in days {
+        let part = |n| runs.iter().find(|r| r.day == day && r.part == n);
+        let ((answer1, time1), (answer2, time2)) = (cell(part(1)), cell(part(2)));
+        rows.push([day.to_string(), answer1, time1, answer2, time2]);
+    }
+
+    let mut widths = [0; 5];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let mut out = String::new();
+    for row in &rows {
+        let mut line = String::new();
+        for (i, (cell, width)) in row.iter().zip(widths).enumerate() {
+            // Times line up on the right, like numbers.
+            let pad = " ".repeat(width - cell.chars().count());
+            match i {
+                2 | 4 => write!(line, "  {pad}{cell}").unwrap(),
+                0 => write!(line, "{cell}{pad}").unwrap(),
+                _ => write!(line, "  {cell}{pad}").unwrap(),
+            }
+        }
+        writeln!(out, "{}", line.trim_end()).unwrap();
+    }
+    for failure in runs.iter().filter_map(Run::failure) {
+        writeln!(out, "\n{failure}").unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::parse::Line;
+
+    fn input(text: &str) -> Input {
+        Input { text: text.to_string(), sample: false, params: vec![] }
+    }
+
+    #[test]
+    fn test_run_all() {
+        let (good, bad) = (input("1"), input("x"));
+        let tasks = [
+            Task {
+                day: "day1",
+                part: 1,
+                solve: |input| Ok(input.text.repeat(2)),
+                input: &good,
+                expected: Some(String::from("11")),
+            },
+            Task {
+                day: "day1",
+                part: 2,
+                solve: |input| Ok(input.text.clone()),
+                input: &good,
+                expected: Some(String::from("2")),
+            },
+            Task {
+                day: "day2",
+                part: 1,
+                solve: |input| panic!("no {}", input.text),
+                input: &bad,
+                expected: None,
+            },
+            Task {
+                day: "day2",
+                part: 2,
+                solve: |_| Ok(String::from("a\nb\n")),
+                input: &bad,
+                expected: None,
+            },
+            Task {
+                day: "day3",
+                part: 1,
+                solve: |input| {
+                    let line = Line::all("day3", &input.text).next().unwrap();
+                    Ok(line.parse::<u32>(line.text, "a number")?.to_string())
+                },
+                input: &bad,
+                expected: Some(String::from("1")),
+            },
+        ];
+        let runs = run_all(&tasks, 3);
+        let passed: Vec<bool> = runs.iter().map(Run::passed).collect();
+        assert_eq!(passed, [true, false, false, true, false]);
+        assert!(
+            matches!(&runs[4].outcome, Outcome::Invalid(report) if report.contains("a number"))
+        );
+        assert_eq!(runs[2].outcome, Outcome::Panic(String::from("no x")));
+
+        let table = table(&runs);
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[0].starts_with("day   part 1  "), "{table}");
+        assert!(lines[1].starts_with("day1  11 ✓    "), "{table}");
+        assert!(lines[1].contains("  1 ✗  "), "{table}");
+        assert!(lines[2].starts_with("day2  panicked ✗"), "{table}");
+        assert!(lines[2].contains("[2 lines]"), "{table}");
+        assert!(table.contains("\nday1 part 2: expected \"2\", got \"1\"\n"), "{table}");
+        assert!(lines[3].starts_with("day3  invalid input ✗"), "{table}");
+        assert!(lines[3].ends_with("  -"), "{table}");
+        assert!(table.contains("\nday2 part 1: panicked: no x\n"), "{table}");
+    }
+}
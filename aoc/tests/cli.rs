@@ -0,0 +1,39 @@
+//! Runs the runner's binary, for how it reads its options.
+
+use std::process::{Command, Output};
+
+fn aoc(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_aoc")).args(args).output().unwrap()
+}
+
+fn stdout(output: &Output) -> String { String::from_utf8_lossy(&output.stdout).into_owned() }
+
+fn stderr(output: &Output) -> String { String::from_utf8_lossy(&output.stderr).into_owned() }
+
+/// `--size` is day 15's parameter but for `gen`, which takes it for the size of the input.
+#[test]
+fn size() {
+    let output = aoc(&["run", "day15", "--input", "test", "--part", "2", "--size", "10"]);
+    assert!(stdout(&output).starts_with("day15 part 2: none "), "{}", stderr(&output));
+    let output = aoc(&["run", "day15", "--input", "test", "--part", "2", "--size", "20"]);
+    assert!(stdout(&output).starts_with("day15 part 2: 56000011 "), "{}", stderr(&output));
+    let output = aoc(&["run", "day15", "--input", "test", "--part", "2", "--size", "-1"]);
+    assert!(stdout(&output).starts_with("day15 part 2: none "), "{}", stderr(&output));
+    let output = aoc(&["run", "day15", "--size", "x"]);
+    assert!(stderr(&output).starts_with("Invalid value x for --size"), "{}", stderr(&output));
+    let output = aoc(&["gen", "day1", "--size", "3", "--seed", "1"]);
+    assert_eq!(stdout(&output).trim_end().split("\n\n").count(), 3);
+}
+
+/// Parameters that would leave nothing to solve are turned away before solving.
+#[test]
+fn ranges() {
+    for (day, name, value) in
+        [("day6", "packet", "0"), ("day11", "relief", "0"), ("day24", "trips", "-1")]
+    {
+        let output = aoc(&["run", day, "--input", "test", &format!("--{name}"), value]);
+        assert_eq!(output.status.code(), Some(2), "{day}");
+        let expected = format!("Invalid value {value} for --{name}");
+        assert!(stderr(&output).starts_with(&expected), "{}", stderr(&output));
+    }
+}
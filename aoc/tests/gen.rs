@@ -0,0 +1,66 @@
+//! Feeds generated inputs of a few sizes and seeds through every day's parser, and its `validate`
+//! for the days that have one.
+
+use aoc::gen::Rng;
+use aoc::Input;
+
+const SIZES: [usize; 4] = [0, 1, 10, 100];
+
+macro_rules! round_trip {
+    ($day:ident) => {
+        #[test]
+        fn $day() {
+            for size in SIZES {
+                for seed in 0..5 {
+                    let input = aoc::gen::$day(&mut Rng::new(seed), size);
+                    if let Err(e) = $day::parse(&input) {
+                        panic!("size {size}, seed {seed}:\n{}", e.report(&input));
+                    }
+                    let day = aoc::find(stringify!($day)).unwrap();
+                    if let Some(validate) = day.validate {
+                        let input = Input { text: input, sample: false, params: Vec::new() };
+                        if let Err(violation) = validate(&input).unwrap() {
+                            panic!("size {size}, seed {seed}: {violation}");
+                        }
+                    }
+                }
+            }
+        }
+    };
+}
+
+round_trip!(day1);
+round_trip!(day2);
+round_trip!(day3);
+round_trip!(day4);
+round_trip!(day5);
+round_trip!(day6);
+round_trip!(day7);
+round_trip!(day8);
+round_trip!(day9);
+round_trip!(day10);
+round_trip!(day11);
+round_trip!(day12);
+round_trip!(day13);
+round_trip!(day14);
+round_trip!(day15);
+round_trip!(day16);
+round_trip!(day17);
+round_trip!(day18);
+round_trip!(day19);
+round_trip!(day20);
+round_trip!(day21);
+round_trip!(day22);
+round_trip!(day23);
+round_trip!(day24);
+round_trip!(day25);
+
+/// Both sides of `root` are built to yell the same number, so part 2 gives back `humn`'s.
+#[test]
+fn day21_solvable() {
+    for seed in 0..5 {
+        let input = aoc::gen::day21(&mut Rng::new(seed), 500);
+        let humn = input.lines().find_map(|line| line.strip_prefix("humn: ")).unwrap();
+        assert_eq!(day21::solve(&input).unwrap().part2.to_string(), humn);
+    }
+}
@@ -0,0 +1,65 @@
+//! Checks every day against the answers recorded in its `answers.txt`, for both the sample and
+//! the real input. Rerecord with `aoc record <day>` after an intended change of answer.
+
+use aoc::answers::normalize;
+use aoc::Input;
+use std::fs;
+
+fn check(name: &str, kind: &str) {
+    let day = aoc::find(name).unwrap();
+    let manifest = day.manifest().unwrap();
+    let text = fs::read_to_string(day.input_path(kind)).unwrap();
+    let input = Input { text, sample: kind == "test", params: Vec::new() };
+
+    let mut failures = Vec::new();
+    for (part, solve) in day.parts() {
+        let answer =
+            normalize(&solve(&input).unwrap_or_else(|e| panic!("{}", e.report(&input.text))));
+        match manifest.get(kind, part) {
+            None => failures.push(format!("{name} {kind} part {part}: no answer recorded")),
+            Some(expected) if expected != answer => failures
+                .push(format!("{name} {kind} part {part}: expected {expected:?}, got {answer:?}")),
+            Some(_) => {}
+        }
+    }
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
+
+macro_rules! golden {
+    ($day:ident $(, #[$input_attr:meta])*) => {
+        mod $day {
+            #[test]
+            fn test() { super::check(stringify!($day), "test") }
+
+            #[test]
+            $(#[$input_attr])*
+            fn input() { super::check(stringify!($day), "input") }
+        }
+    };
+}
+
+golden!(day1);
+golden!(day2);
+golden!(day3);
+golden!(day4);
+golden!(day5);
+golden!(day6);
+golden!(day7);
+golden!(day8);
+golden!(day9);
+golden!(day10);
+golden!(day11);
+golden!(day12);
+golden!(day13);
+golden!(day14);
+golden!(day15);
+golden!(day16, #[ignore = "takes minutes, run with `cargo test -r -p aoc -- --ignored`"]);
+golden!(day17);
+golden!(day18);
+golden!(day19);
+golden!(day20);
+golden!(day21);
+golden!(day22);
+golden!(day23);
+golden!(day24);
+golden!(day25);
@@ -0,0 +1,72 @@
+//! Checks that the sample and real inputs hold to what every day's solution assumes, and that
+//! inputs breaking an assumption are told apart by which one.
+
+use aoc::gen::Rng;
+use aoc::{Input, DAYS};
+use aoc_common::Violation;
+use std::fs;
+
+fn validate(name: &str, text: String) -> Result<(), Violation> {
+    let validate = aoc::find(name).unwrap().validate.unwrap();
+    let input = Input { text, sample: false, params: Vec::new() };
+    validate(&input).unwrap_or_else(|e| panic!("{}", e.report(&input.text)))
+}
+
+fn assumption(name: &str, text: String) -> String { validate(name, text).unwrap_err().assumption }
+
+#[test]
+fn inputs() {
+    for day in DAYS.iter().filter(|day| day.validate.is_some()) {
+        for kind in ["test", "input"] {
+            let text = fs::read_to_string(day.input_path(kind)).unwrap();
+            let input = Input { text, sample: kind == "test", params: Vec::new() };
+            match (day.validate.unwrap())(&input) {
+                Ok(Ok(())) => {}
+                Ok(Err(violation)) => panic!("{kind}: {violation}"),
+                Err(e) => panic!("{} {kind}:\n{}", day.name, e.report(&input.text)),
+            }
+        }
+    }
+}
+
+#[test]
+fn day5() {
+    let text = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 4 from 2 to 1\n";
+    assert_eq!(assumption("day5", text.into()), "moves taking only the crates there are");
+}
+
+#[test]
+fn day12() {
+    assert!(validate("day12", "SbcE\n".into()).is_ok());
+    assert_eq!(assumption("day12", "SbSE\n".into()), "exactly one `S` on the map");
+    assert_eq!(assumption("day12", "SEcE\n".into()), "exactly one `E` on the map");
+}
+
+#[test]
+fn day16() {
+    let valves: Vec<String> = (b'A'..=b'Q').map(|c| format!("B{}", c as char)).collect();
+    let mut text =
+        format!("Valve AA has flow rate=0; tunnels lead to valves {}\n", valves.join(", "));
+    for valve in &valves {
+        text += &format!("Valve {valve} has flow rate=1; tunnel leads to valve AA\n");
+    }
+    assert_eq!(assumption("day16", text), "at most 16 valves with flow");
+}
+
+#[test]
+fn day22() {
+    assert!(validate("day22", aoc::gen::day22(&mut Rng::new(0), 50)).is_ok());
+    let text = "  ....\n  ....\n  ..\n  ..\n....\n....\n..\n..\n\n1\n";
+    assert_eq!(assumption("day22", text.into()), "faces of 4 or 50 tiles across");
+}
+
+#[test]
+fn day24() {
+    let open = "#.####\n#>..<#\n#.<..#\n####.#\n";
+    assert!(validate("day24", open.into()).is_ok());
+    let blocked = "#.####\n#v..<#\n#.<..#\n####.#\n";
+    assert_eq!(
+        assumption("day24", blocked.into()),
+        "no blizzards going up or down the columns of the openings"
+    );
+}
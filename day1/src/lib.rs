@@ -0,0 +1,381 @@
+use aoc_common::{params, Answers, Line, ParseError};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap};
+use std::fmt;
+
+pub const DAY: &str = "day1";
+
+/// The food an elf carries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf {
+    /// Where the elf comes in the input, counting from 1 as the puzzle does.
+    pub index: usize,
+    /// Calories of each item.
+    pub items: Vec<u64>,
+}
+
+impl Elf {
+    /// Calories of all items, which `parse` checks fit a `u64`.
+    pub fn total(&self) -> u64 { self.items.iter().sum() }
+}
+
+/// The calories on `line`, and an elf's `total` with them added.
+fn add(line: &Line, total: u64) -> Result<(u64, u64), ParseError> {
+    let calories = line.parse_all::<u64>("a number of calories")?;
+    let total = total
+        .checked_add(calories)
+        .ok_or_else(|| line.error(line.text, "calories adding up to at most 2^64 - 1"))?;
+    Ok((calories, total))
+}
+
+/// The elves in the order of the input, each with its items.
+pub fn parse(input: &str) -> Result<Vec<Elf>, ParseError> {
+    let mut elves = vec![Elf { index: 1, items: Vec::new() }];
+    let mut total = 0;
+
+    for line in Line::all(DAY, input) {
+        if line.text.is_empty() {
+            elves.push(Elf { index: elves.len() + 1, items: Vec::new() });
+            total = 0;
+            continue;
+        }
+        let (calories, sum) = add(&line, total)?;
+        elves.last_mut().unwrap().items.push(calories);
+        total = sum;
+    }
+    Ok(elves)
+}
+
+/// The `k` largest of the items pushed, kept in a min-heap of at most `k` items, so that memory
+/// doesn't grow with the number of items.
+#[derive(Debug, Clone)]
+pub struct TopK<T> {
+    k: usize,
+    heap: BinaryHeap<Reverse<T>>,
+}
+
+impl<T: Ord> TopK<T> {
+    pub fn new(k: usize) -> TopK<T> { TopK { k, heap: BinaryHeap::new() } }
+
+    pub fn push(&mut self, item: T) {
+        if self.heap.len() < self.k {
+            self.heap.push(Reverse(item));
+        } else if let Some(mut least) = self.heap.peek_mut() {
+            if item > least.0 {
+                *least = Reverse(item);
+            }
+        }
+    }
+
+    /// The items kept, largest first.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        self.heap.into_sorted_vec().into_iter().map(|Reverse(item)| item).collect()
+    }
+}
+
+/// How far off `Sketch` quantiles may be, relative to the true value.
+pub const ACCURACY: f64 = 0.01;
+
+/// Ratio between the bounds of a `Sketch` bucket.
+const GAMMA: f64 = (1.0 + ACCURACY) / (1.0 - ACCURACY);
+
+/// Approximate quantiles of a stream of numbers in little memory (a DDSketch). Numbers are only
+/// counted, in buckets of numbers within `ACCURACY` of each other, so that a quantile is off by at
+/// most that, and all of `u64` takes a couple thousand buckets at most.
+#[derive(Debug, Clone, Default)]
+pub struct Sketch {
+    count: u64,
+    zeros: u64,
+    /// Numbers above 0 by bucket, where bucket `i` has those above `GAMMA^(i - 1)` up to
+    /// `GAMMA^i`.
+    buckets: BTreeMap<i32, u64>,
+}
+
+impl Sketch {
+    pub fn new() -> Sketch { Sketch::default() }
+
+    pub fn add(&mut self, value: u64) {
+        self.count += 1;
+        if value == 0 {
+            self.zeros += 1;
+        } else {
+            let bucket = ((value as f64).ln() / GAMMA.ln()).ceil() as i32;
+            *self.buckets.entry(bucket).or_default() += 1;
+        }
+    }
+
+    /// How many numbers were added.
+    pub fn count(&self) -> u64 { self.count }
+
+    /// The number a fraction `q` (from 0 to 1) of the way through the numbers in order, or `None`
+    /// without numbers.
+    pub fn quantile(&self, q: f64) -> Option<u64> {
+        if self.count == 0 {
+            return None;
+        }
+        let rank = (q.clamp(0.0, 1.0) * (self.count - 1) as f64).round() as u64;
+        if rank < self.zeros {
+            return Some(0);
+        }
+        let mut seen = self.zeros;
+        let (&bucket, _) = self.buckets.iter().find(|(_, &n)| {
+            seen += n;
+            seen > rank
+        })?;
+        // The middle of the bucket in relative terms, so within `ACCURACY` of all of it.
+        Some((2.0 * GAMMA.powi(bucket) / (GAMMA + 1.0)).round() as u64)
+    }
+
+    /// The `p`th percentile, `p` from 0 to 100.
+    pub fn percentile(&self, p: f64) -> Option<u64> { self.quantile(p / 100.0) }
+
+    pub fn median(&self) -> Option<u64> { self.quantile(0.5) }
+}
+
+/// An elf among those carrying the most calories.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ranked {
+    /// Place in the ranking, from 1.
+    pub rank: usize,
+    pub index: usize,
+    pub items: usize,
+    pub total: u64,
+    /// Fraction of the calories carried by all elves, from 0 to 1.
+    pub share: f64,
+}
+
+impl fmt::Display for Ranked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Ranked { rank, index, items, total, share } = self;
+        let s = if *items == 1 { "" } else { "s" };
+        let percent = share * 100.0;
+        write!(f, "{rank}. elf {index}: {total} calories in {items} item{s} ({percent:.1}%)")
+    }
+}
+
+/// An elf's total, index and number of items, ordered by most calories and then by coming first.
+type Entry = (u64, Reverse<usize>, usize);
+
+/// The elves of `top`, out of elves carrying `all` calories together.
+fn ranking(top: TopK<Entry>, all: u128) -> Vec<Ranked> {
+    top.into_sorted_vec()
+        .into_iter()
+        .enumerate()
+        .map(|(i, (total, Reverse(index), items))| Ranked {
+            rank: i + 1,
+            index,
+            items,
+            total,
+            share: if all == 0 { 0.0 } else { total as f64 / all as f64 },
+        })
+        .collect()
+}
+
+/// The `n` elves carrying the most calories, most first, or all of them if there are fewer. Elves
+/// carrying as much keep their order.
+pub fn top(elves: &[Elf], n: usize) -> Vec<Ranked> {
+    let mut top = TopK::new(n);
+    for elf in elves {
+        top.push((elf.total(), Reverse(elf.index), elf.items.len()));
+    }
+    ranking(top, elves.iter().map(|elf| elf.total() as u128).sum())
+}
+
+/// Elves totalled line by line, for inputs too big to keep: only the elf being read, the `k`
+/// carrying the most so far and a `Sketch` of all totals are kept.
+#[derive(Debug, Clone)]
+pub struct Stream {
+    top: TopK<Entry>,
+    totals: Sketch,
+    all: u128,
+    max: u64,
+    /// Elves read to the end.
+    elves: usize,
+    /// Calories and items of the elf being read.
+    total: u64,
+    items: usize,
+}
+
+/// What a `Stream` found once the input ended.
+#[derive(Debug, Clone)]
+pub struct Tally {
+    pub elves: usize,
+    /// Calories of the elf carrying the most.
+    pub max: u64,
+    /// The `k` elves carrying the most, as `top` ranks them.
+    pub top: Vec<Ranked>,
+    /// The totals of all elves, for their percentiles.
+    pub totals: Sketch,
+}
+
+impl Stream {
+    pub fn new(k: usize) -> Stream {
+        Stream {
+            top: TopK::new(k),
+            totals: Sketch::new(),
+            all: 0,
+            max: 0,
+            elves: 0,
+            total: 0,
+            items: 0,
+        }
+    }
+
+    /// Reads the next line of the input.
+    pub fn push(&mut self, line: &Line) -> Result<(), ParseError> {
+        if line.text.is_empty() {
+            self.end_elf();
+        } else {
+            (_, self.total) = add(line, self.total)?;
+            self.items += 1;
+        }
+        Ok(())
+    }
+
+    fn end_elf(&mut self) {
+        self.elves += 1;
+        self.top.push((self.total, Reverse(self.elves), self.items));
+        self.totals.add(self.total);
+        self.all += self.total as u128;
+        self.max = self.max.max(self.total);
+        (self.total, self.items) = (0, 0);
+    }
+
+    /// Ends the last elf, at the end of the input.
+    pub fn finish(mut self) -> Tally {
+        self.end_elf();
+        let top = ranking(self.top, self.all);
+        Tally { elves: self.elves, max: self.max, top, totals: self.totals }
+    }
+}
+
+params! {
+    pub struct Params {
+        /// Elves whose calories add up in part 2.
+        top: usize = 3,
+    }
+}
+
+pub fn part1(elves: &[Elf], _: &Params) -> u64 { elves.iter().map(Elf::total).max().unwrap_or(0) }
+
+pub fn part2(elves: &[Elf], params: &Params) -> u64 {
+    top(elves, params.top).iter().map(|elf| elf.total).sum()
+}
+
+pub fn solve(input: &str, params: &Params) -> Result<Answers<u64>, ParseError> {
+    Ok(Answers::solve(parse(input)?.as_slice(), |e| part1(e, params), |e| part2(e, params)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::params::Params as _;
+    use proptest::prelude::*;
+
+    const INPUT: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+    fn tally(input: &str, k: usize) -> Tally {
+        let mut stream = Stream::new(k);
+        for line in Line::all(DAY, input) {
+            stream.push(&line).unwrap();
+        }
+        stream.finish()
+    }
+
+    #[test]
+    fn test_parse() {
+        let elves = parse(INPUT).unwrap();
+        assert_eq!(elves.len(), 5);
+        assert_eq!(elves[3], Elf { index: 4, items: vec![7000, 8000, 9000] });
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse("100\n\n1x0\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_deref()), (3, 1, Some("1x0")));
+        assert!(parse("18446744073709551615\n1\n").is_err());
+        let elves = parse("4294967296\n4294967296\n").unwrap();
+        assert_eq!(part1(&elves, &Params::puzzle()), 1 << 33);
+    }
+
+    #[test]
+    fn test_top() {
+        let elves = parse(INPUT).unwrap();
+        let ranked = top(&elves, 3);
+        assert_eq!(ranked.iter().map(|elf| elf.index).collect::<Vec<_>>(), [4, 3, 5]);
+        assert_eq!(ranked[0].to_string(), "1. elf 4: 24000 calories in 3 items (43.6%)");
+        assert_eq!(ranked[2].to_string(), "3. elf 5: 10000 calories in 1 item (18.2%)");
+        assert_eq!(top(&elves, 10).len(), 5);
+    }
+
+    #[test]
+    fn test_ties_and_few_elves() {
+        let elves = parse("5\n\n3\n\n5\n").unwrap();
+        let ranked = top(&elves, 3);
+        assert_eq!(ranked.iter().map(|elf| elf.index).collect::<Vec<_>>(), [1, 3, 2]);
+        assert_eq!(part2(&elves[..2], &Params { top: 3 }), 8);
+        assert_eq!(part1(&[], &Params::puzzle()), 0);
+    }
+
+    #[test]
+    fn test_stream() {
+        let tally = tally(INPUT, 3);
+        assert_eq!((tally.elves, tally.max), (5, 24000));
+        assert_eq!(tally.top, top(&parse(INPUT).unwrap(), 3));
+        let median = tally.totals.median().unwrap();
+        assert!(median.abs_diff(10000) <= 100, "{median}");
+    }
+
+    #[test]
+    fn test_sketch() {
+        let mut sketch = Sketch::new();
+        assert_eq!(sketch.median(), None);
+        for value in 0..=100000 {
+            sketch.add(value);
+        }
+        assert_eq!(sketch.count(), 100001);
+        assert_eq!(sketch.percentile(0.0), Some(0));
+        for p in [1.0, 25.0, 50.0, 90.0, 99.0, 100.0] {
+            let exact = p * 1000.0;
+            let found = sketch.percentile(p).unwrap() as f64;
+            assert!((found - exact).abs() <= exact * ACCURACY, "{p}: {found}");
+        }
+        sketch.add(u64::MAX);
+        assert!(sketch.percentile(100.0).unwrap() > u64::MAX / 100 * 98);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_top_k_matches_sort(
+            items in prop::collection::vec(0..50u32, 0..100),
+            k in 0..10usize,
+        ) {
+            let mut top = TopK::new(k);
+            for &item in &items {
+                top.push(item);
+            }
+            let mut sorted = items.clone();
+            sorted.sort_by(|a, b| b.cmp(a));
+            sorted.truncate(k);
+            prop_assert_eq!(top.into_sorted_vec(), sorted);
+        }
+
+        #[test]
+        fn prop_sketch_within_accuracy(
+            values in prop::collection::vec(0..1000000u64, 1..200),
+            q in 0.0..=1.0f64,
+        ) {
+            let mut sketch = Sketch::new();
+            for &value in &values {
+                sketch.add(value);
+            }
+            let mut sorted = values.clone();
+            sorted.sort();
+            let exact = sorted[(q * (sorted.len() - 1) as f64).round() as usize] as f64;
+            let found = sketch.quantile(q).unwrap() as f64;
+            // Rounding to a whole number may add half of one.
+            let bound = exact * ACCURACY + 0.5;
+            prop_assert!((found - exact).abs() <= bound, "{} for {}", found, exact);
+        }
+    }
+}
@@ -1,25 +1,40 @@
-use std::collections::BinaryHeap;
-use std::io::{self, BufRead};
+use aoc_common::{input, params, Line};
+use std::process::exit;
 
 fn main() {
-    let mut heap = BinaryHeap::new();
-    let mut cur_elf = 0;
-
-    for line in io::stdin().lock().lines() {
-        let line = line.unwrap();
-        if line == "" {
-            heap.push(cur_elf);
-            cur_elf = 0;
-            continue;
+    // Streams the input, so that it can be bigger than memory. `--top N` ranks the N elves
+    // carrying the most calories under the answers, and percentiles of all elves follow.
+    let args: params::Args<day1::Params> = params::args();
+    let lines = input::lines(&args.input).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        exit(1)
+    });
+    let mut stream = day1::Stream::new(args.params.top);
+    for (i, text) in lines.enumerate() {
+        let text = text.unwrap_or_else(|e| {
+            eprintln!("Can't read {}: {e}", args.input);
+            exit(1)
+        });
+        let line = Line { day: day1::DAY, number: i + 1, text: &text };
+        if let Err(e) = stream.push(&line) {
+            eprintln!("{}", e.report_line(&text));
+            exit(1)
         }
-        cur_elf += line.parse::<i32>().unwrap();
     }
-    heap.push(cur_elf);
-
-    let mut top3 = 0;
-    top3 += heap.pop().unwrap();
-    println!("Step 1: max is {}", top3);
-    top3 += heap.pop().unwrap();
-    top3 += heap.pop().unwrap();
-    println!("Step 2: top3 is {}", top3);
+    let tally = stream.finish();
+    let top: u64 = tally.top.iter().map(|elf| elf.total).sum();
+    println!("Step 1: max is {}", tally.max);
+    println!("Step 2: top{} is {top}", args.params.top);
+    for elf in &tally.top {
+        println!("{elf}");
+    }
+    let percentile = |p| tally.totals.percentile(p).unwrap();
+    println!(
+        "{} elves, carrying about {} calories at the median, {} at the 90th and {} at the 99th \
+         percentile",
+        tally.elves,
+        percentile(50.0),
+        percentile(90.0),
+        percentile(99.0)
+    );
 }
@@ -0,0 +1,100 @@
+use aoc_common::{params, Answers, Line, ParseError, Violation};
+
+const DAY: &str = "day10";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Instr {
+    Noop,
+    Addx(i32),
+}
+
+pub fn parse(input: &str) -> Result<Vec<Instr>, ParseError> {
+    Line::all(DAY, input)
+        .map(|line| {
+            let mut parts = line.text.split_whitespace();
+            match line.require(parts.next(), "an instruction")? {
+                "noop" => Ok(Instr::Noop),
+                "addx" => Ok(Instr::Addx(
+                    line.parse(line.require(parts.next(), "a number")?, "a number")?,
+                )),
+                other => Err(line.error(other, "`noop` or `addx`")),
+            }
+        })
+        .collect()
+}
+
+/// Runs the program, calling `tick` with the value of X during every cycle.
+fn run<F>(program: &[Instr], mut tick: F)
+where
+    F: FnMut(i32),
+{
+    let mut x = 1;
+    for instr in program {
+        match instr {
+            Instr::Noop => tick(x),
+            Instr::Addx(n) => {
+                tick(x);
+                tick(x);
+                x += n;
+            }
+        }
+    }
+}
+
+params! {
+    pub struct Params {
+        /// First cycle whose signal strength counts in part 1.
+        first: i32 = 20,
+        /// Cycles between the ones whose signal strength counts.
+        interval: i32 = 40,
+        /// Pixels in a row of the CRT.
+        width: usize = 40,
+    }
+}
+
+/// The program draws whole rows of the CRT.
+pub fn validate(program: &[Instr], params: &Params) -> Result<(), Violation> {
+    let mut cycles = 0;
+    run(program, |_| cycles += 1);
+    if params.width == 0 || cycles % params.width != 0 {
+        let found = format!("{cycles} cycles for rows of {}", params.width);
+        return Err(Violation::new(DAY, "a program drawing whole rows", found));
+    }
+    Ok(())
+}
+
+pub fn part1(program: &[Instr], params: &Params) -> i32 {
+    let mut result = 0;
+    let mut cycle = 0;
+    let mut interesting = params.first;
+    let interval = params.interval;
+
+    run(program, |x| {
+        cycle += 1;
+        if cycle == interesting {
+            result += x * interesting;
+            interesting += interval
+        }
+    });
+    result
+}
+
+pub fn part2(program: &[Instr], params: &Params) -> String {
+    let mut screen = String::new();
+    let mut current_line = String::new();
+    run(program, |x| {
+        let pos = current_line.len() as i32;
+        current_line += if x - 1 <= pos && pos <= x + 1 { "#" } else { "." };
+        if current_line.len() == params.width {
+            screen += &current_line;
+            screen += "\n";
+            current_line = String::new();
+        }
+    });
+    screen
+}
+
+/// Part 1 is the signal strength, part 2 the image drawn on the CRT.
+pub fn solve(input: &str, params: &Params) -> Result<Answers<i32, String>, ParseError> {
+    Ok(Answers::solve(parse(input)?.as_slice(), |p| part1(p, params), |p| part2(p, params)))
+}
@@ -1,45 +1,24 @@
-use std::io::{self, BufRead};
+use aoc_common::{input, params};
+use std::process::exit;
 
 fn main() {
-    let mut x = 1;
-
-    // For part 1.
-    let mut result = 0;
-    let mut cycle = 0;
-    let mut interesting = 20;
-    let interval = 40;
-
-    // For part 2.
-    let mut current_line = String::new();
-    let mut draw = |x| {
-        let pos = current_line.len() as i32;
-        current_line += if x - 1 <= pos && pos <= x + 1 { "#" } else { "." };
-        if current_line.len() == 40 {
-            println!("{}", current_line);
-            current_line = String::new();
-        }
-    };
-
-    for line in io::stdin().lock().lines() {
-        let line = line.unwrap();
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        match parts[0] {
-            "noop" => {
-                cycle += 1;
-                draw(x);
-            }
-            "addx" => {
-                cycle += 2;
-                draw(x);
-                draw(x);
-                if cycle >= interesting {
-                    result += x * interesting;
-                    interesting += interval
-                }
-                x += parts[1].parse::<i32>().unwrap();
-            }
-            _ => panic!("Invalid instruction"),
-        }
+    let args = params::args();
+    let input = input::read(&args.input).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        exit(1)
+    });
+    let program = day10::parse(&input).unwrap_or_else(|e| {
+        eprintln!("{}", e.report(&input));
+        exit(1)
+    });
+    if let Err(violation) = day10::validate(&program, &args.params) {
+        eprintln!("{violation}");
+        exit(1)
     }
-    println!("Part 1: {}", result);
+    let answers = day10::solve(&input, &args.params).unwrap_or_else(|e| {
+        eprintln!("{}", e.report(&input));
+        exit(1)
+    });
+    print!("{}", answers.part2);
+    println!("Part 1: {}", answers.part1);
 }
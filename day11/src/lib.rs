@@ -0,0 +1,169 @@
+use aoc_common::parse::end_of_input;
+use aoc_common::{params, Answers, Line, ParseError, Violation};
+use num::Integer;
+use regex::{Captures, Regex};
+use std::num::NonZeroUsize;
+
+const DAY: &str = "day11";
+
+pub type ItemType = i64;
+/// Worry levels of the items held by each monkey.
+pub type State = Vec<Vec<ItemType>>;
+
+pub enum Op {
+    Add(ItemType),
+    Mul(ItemType),
+    Square,
+}
+
+pub struct Monkey {
+    pub op: Op,
+    pub test: ItemType,
+    pub if_true: usize,
+    pub if_false: usize,
+}
+
+/// The next line, which must match `re`.
+fn next_line<'a>(
+    input: &'a str,
+    lines: &mut impl Iterator<Item = Line<'a>>,
+    re: &Regex,
+    expected: &str,
+) -> Result<(Line<'a>, Captures<'a>), ParseError> {
+    let line = lines.next().ok_or_else(|| end_of_input(DAY, input, expected))?;
+    let captures = re.captures(line.text).ok_or_else(|| line.error(line.text, expected))?;
+    Ok((line, captures))
+}
+
+pub fn parse(input: &str) -> Result<(Vec<Monkey>, State), ParseError> {
+    let monkey_re = Regex::new(r"Monkey \d+:").unwrap();
+    let starting_re = Regex::new(r" {2}Starting items: (.*)").unwrap();
+    let operation_re = Regex::new(r" {2}Operation: new = old (\+ \d+|\* \d+|\* old)").unwrap();
+    let test_re = Regex::new(r" {2}Test: divisible by (\d+)").unwrap();
+    let true_re = Regex::new(r" {4}If true: throw to monkey (\d+)").unwrap();
+    let false_re = Regex::new(r" {4}If false: throw to monkey (\d+)").unwrap();
+
+    let mut monkeys = Vec::new();
+    let mut state = Vec::new();
+
+    let mut iter = Line::all(DAY, input).peekable();
+    while iter.peek().is_some() {
+        next_line(input, &mut iter, &monkey_re, "`Monkey <n>:`")?;
+        let (line, sm) = next_line(input, &mut iter, &starting_re, "`  Starting items: <items>`")?;
+        state.push(
+            sm.get(1)
+                .unwrap()
+                .as_str()
+                .split(',')
+                .map(|s| line.parse(s.trim(), "a worry level"))
+                .collect::<Result<_, _>>()?,
+        );
+        let (line, op_matches) =
+            next_line(input, &mut iter, &operation_re, "`  Operation: new = old <op> <n>`")?;
+        let op_parts: Vec<&str> = op_matches.get(1).unwrap().as_str().split_whitespace().collect();
+        let (test_line, test_matches) =
+            next_line(input, &mut iter, &test_re, "`  Test: divisible by <n>`")?;
+        let (true_line, true_matches) =
+            next_line(input, &mut iter, &true_re, "`    If true: throw to monkey <n>`")?;
+        let (false_line, false_matches) =
+            next_line(input, &mut iter, &false_re, "`    If false: throw to monkey <n>`")?;
+        iter.next(); // newline
+
+        monkeys.push(Monkey {
+            op: match op_parts[..] {
+                ["*", "old"] => Op::Square,
+                ["*", n] => Op::Mul(line.parse(n, "a number")?),
+                [_, n] => Op::Add(line.parse(n, "a number")?),
+                _ => unreachable!("The regex only matches two-part operations"),
+            },
+            test: test_line.parse(test_matches.get(1).unwrap().as_str(), "a divisor")?,
+            if_true: true_line.parse(true_matches.get(1).unwrap().as_str(), "a monkey")?,
+            if_false: false_line.parse(false_matches.get(1).unwrap().as_str(), "a monkey")?,
+        });
+    }
+
+    Ok((monkeys, state))
+}
+
+fn run<F>(monkeys: &[Monkey], mut state: State, rounds: usize, manage: F) -> ItemType
+where
+    F: Fn(ItemType) -> ItemType,
+{
+    let mut inspections = vec![0; state.len()];
+    for _ in 0..rounds {
+        for (i, monkey) in monkeys.iter().enumerate() {
+            for item in state[i].clone() {
+                inspections[i] += 1;
+                let item = match monkey.op {
+                    Op::Add(n) => item + n,
+                    Op::Mul(n) => item * n,
+                    Op::Square => item * item,
+                };
+                let item = manage(item);
+                let dest_monkey =
+                    if item % monkey.test == 0 { monkey.if_true } else { monkey.if_false };
+                state[dest_monkey].push(item);
+            }
+            state[i].clear()
+        }
+    }
+    inspections.sort();
+    inspections.reverse();
+    inspections[0..2].iter().product()
+}
+
+params! {
+    pub struct Params {
+        /// Rounds played in part 1.
+        rounds: usize = 20,
+        /// What worry levels are divided by after each inspection in part 1.
+        relief: NonZeroUsize = NonZeroUsize::new(3).unwrap(),
+        /// Rounds played in part 2, without relief.
+        worried_rounds: usize = 10000,
+    }
+}
+
+/// At least two monkeys, throwing only to other monkeys, testing by a divisor other than 0, and
+/// whose operations keep worry levels below the product of the divisors within 64 bits.
+pub fn validate((monkeys, _): &(Vec<Monkey>, State)) -> Result<(), Violation> {
+    if monkeys.len() < 2 {
+        return Err(Violation::new(DAY, "at least two monkeys", monkeys.len().to_string()));
+    }
+    for (i, monkey) in monkeys.iter().enumerate() {
+        for to in [monkey.if_true, monkey.if_false] {
+            if to == i || to >= monkeys.len() {
+                let found = format!("monkey {i} throwing to monkey {to}");
+                return Err(Violation::new(DAY, "throws to the other monkeys", found));
+            }
+        }
+        if monkey.test == 0 {
+            return Err(Violation::new(DAY, "divisors other than 0", format!("0 for monkey {i}")));
+        }
+    }
+    let lcm = monkeys.iter().map(|m| m.test).reduce(|a, b| a.lcm(&b)).unwrap();
+    for (i, monkey) in monkeys.iter().enumerate() {
+        let highest = match monkey.op {
+            Op::Add(n) => (lcm - 1).checked_add(n),
+            Op::Mul(n) => (lcm - 1).checked_mul(n),
+            Op::Square => (lcm - 1).checked_mul(lcm - 1),
+        };
+        if highest.is_none() {
+            let found = format!("an overflow for monkey {i} with the divisors' product {lcm}");
+            return Err(Violation::new(DAY, "worry levels fitting in 64 bits", found));
+        }
+    }
+    Ok(())
+}
+
+pub fn part1((monkeys, initial_state): &(Vec<Monkey>, State), params: &Params) -> ItemType {
+    run(monkeys, initial_state.clone(), params.rounds, |i| i / params.relief.get() as ItemType)
+}
+
+pub fn part2((monkeys, initial_state): &(Vec<Monkey>, State), params: &Params) -> ItemType {
+    let lcm: ItemType = monkeys.iter().map(|m| m.test).reduce(|a, b| a.lcm(&b)).unwrap();
+    run(monkeys, initial_state.clone(), params.worried_rounds, |i| i % lcm)
+}
+
+pub fn solve(input: &str, params: &Params) -> Result<Answers<ItemType>, ParseError> {
+    Ok(Answers::solve(&parse(input)?, |m| part1(m, params), |m| part2(m, params)))
+}
@@ -0,0 +1,91 @@
+use aoc_common::parse::end_of_input;
+use aoc_common::picture::{shades, Picture};
+use aoc_common::search::{bfs, flood};
+use aoc_common::{verbose, Answers, BBox2, Grid, ParseError, Point2, Violation};
+use std::collections::HashSet;
+
+const DAY: &str = "day12";
+
+type Pos = Point2<i32>;
+
+fn height(c: char) -> u8 {
+    match c {
+        'S' => 0,
+        'E' => 25,
+        _ => c as u8 - b'a',
+    }
+}
+
+/// The height map, with the start marked `S` and the end `E`.
+pub fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+    let expected = "a height from a to z, `S` or `E`";
+    let map = Grid::try_parse(DAY, input, expected, |c| {
+        (c.is_ascii_lowercase() || c == 'S' || c == 'E').then_some(c)
+    })?;
+    for marker in ['S', 'E'] {
+        if map.find::<i32, _>(|c| *c == marker).is_none() {
+            return Err(end_of_input(DAY, input, format!("`{marker}` on the map")));
+        }
+    }
+    Ok(map)
+}
+
+/// Exactly one start and one end on the map.
+pub fn validate(map: &Grid<char>) -> Result<(), Violation> {
+    for marker in ['S', 'E'] {
+        let count = map.iter::<i32>().filter(|(_, c)| **c == marker).count();
+        if count != 1 {
+            let assumption = format!("exactly one `{marker}` on the map");
+            return Err(Violation::new(DAY, assumption, count.to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// Where the climb to `pos` can come from, i.e. the steps walking down from it.
+fn down(heights: &Grid<u8>, pos: &Pos) -> Vec<Pos> {
+    let min_height = heights[*pos].saturating_sub(1);
+    heights.neighbours4(*pos).filter(|next| heights[*next] >= min_height).collect()
+}
+
+/// Walks down from the end, returning the distance to the start and to the nearest lowest point.
+fn climb(map: &Grid<char>) -> (usize, usize) {
+    let start: Pos = map.find(|c| *c == 'S').unwrap();
+    let end: Pos = map.find(|c| *c == 'E').unwrap();
+    let heights = map.map(|c| height(*c));
+
+    let reached = flood(end, |pos: &Pos| down(&heights, pos));
+    verbose!("Search: {}", reached.stats);
+    let to_start = *reached.dist.get(&start).expect("No route to the start");
+    let to_lowest =
+        reached.dist.iter().filter(|(pos, _)| heights[**pos] == 0).map(|(_, d)| *d).min().unwrap();
+    (to_start, to_lowest)
+}
+
+pub fn part1(map: &Grid<char>) -> usize { climb(map).0 }
+
+pub fn part2(map: &Grid<char>) -> usize { climb(map).1 }
+
+/// The height map in greens, with the route of part 1 in red.
+pub fn picture(map: &Grid<char>) -> Picture {
+    const ROUTE: u8 = 26;
+    let start: Pos = map.find(|c| *c == 'S').unwrap();
+    let end: Pos = map.find(|c| *c == 'E').unwrap();
+    let heights = map.map(|c| height(*c));
+    let route: HashSet<Pos> = bfs(end, |pos: &Pos| down(&heights, pos), |pos| *pos == start)
+        .map_or(HashSet::new(), |found| found.path.into_iter().collect());
+
+    let bounds = BBox2 {
+        min: Pos::origin(),
+        max: Pos { x: map.width() as i32 - 1, y: map.height() as i32 - 1 },
+    };
+    let mut palette = shades([10, 50, 10], [220, 250, 200], 26);
+    palette.push([220, 30, 30]);
+    Picture::draw(&bounds, palette, |p| if route.contains(&p) { ROUTE } else { heights[p] })
+}
+
+/// Both answers come out of a single search.
+pub fn solve(input: &str) -> Result<Answers<usize>, ParseError> {
+    let (part1, part2) = climb(&parse(input)?);
+    Ok(Answers { part1, part2 })
+}
@@ -0,0 +1,209 @@
+use aoc_common::parse::end_of_input;
+use aoc_common::{Answers, Line, ParseError};
+use std::cmp::Ordering;
+use std::fmt;
+
+const DAY: &str = "day13";
+
+/// A packet, or a value inside one. Packets compare the way the puzzle orders them, so `1`, `[1]`
+/// and `[[1]]` are all equal.
+#[derive(Debug, Clone)]
+pub enum El {
+    Num(u32),
+    List(Vec<El>),
+}
+
+use El::*;
+
+macro_rules! list {
+    ($expr:expr) => {
+        List(vec![$expr])
+    };
+}
+
+impl Ord for El {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Num(a), Num(b)) => a.cmp(b),
+            (Num(_), List(_)) => list!(self.clone()).cmp(other),
+            (List(_), Num(_)) => self.cmp(&list!(other.clone())),
+            // Item by item, and a list running out first is the smaller.
+            (List(aa), List(bb)) => aa.cmp(bb),
+        }
+    }
+}
+
+impl PartialEq for El {
+    fn eq(&self, other: &Self) -> bool { self.cmp(other) == Ordering::Equal }
+}
+
+impl Eq for El {}
+
+impl PartialOrd for El {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+/// Writes a packet the way the input has it, e.g. `[1,[2,3]]`.
+impl fmt::Display for El {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Num(n) => write!(f, "{n}"),
+            List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+/// The first character of `s`, to point errors at.
+fn first_char(s: &str) -> &str { &s[..s.chars().next().map_or(0, char::len_utf8)] }
+
+/// How deep lists may nest. Comparing, writing and dropping packets all recurse into their lists,
+/// so deeper ones are turned away while parsing rather than overflowing the stack later.
+pub const MAX_DEPTH: usize = 64;
+
+/// Parses a packet at the start of `s`, inside `depth` lists, returning what follows it.
+fn parse_el<'a>(line: Line<'a>, mut s: &'a str, depth: usize) -> Result<(El, &'a str), ParseError> {
+    if let Some(rest) = s.strip_prefix('[') {
+        if depth == MAX_DEPTH {
+            return Err(line.error(first_char(s), format!("at most {MAX_DEPTH} nested lists")));
+        }
+        let mut content = Vec::new();
+        s = rest;
+        loop {
+            if let Some(rest) = s.strip_prefix(']') {
+                return Ok((List(content), rest));
+            }
+            let (parsed, rest) = parse_el(line, s, depth + 1)?;
+            s = rest;
+            content.push(parsed);
+            if let Some(rest) = s.strip_prefix(',') {
+                s = rest;
+            } else if !s.starts_with(']') {
+                return Err(line.error(first_char(s), "`,` or `]`"));
+            }
+        }
+    } else {
+        let digits = s.len() - s.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        if digits == 0 {
+            return Err(line.error(first_char(s), "`[` or a number"));
+        }
+        Ok((Num(line.parse(&s[..digits], "a number")?), &s[digits..]))
+    }
+}
+
+/// Parses a line holding exactly one packet.
+fn parse_packet(input: &str, line: Option<Line>) -> Result<El, ParseError> {
+    let line = line.ok_or_else(|| end_of_input(DAY, input, "a packet"))?;
+    match parse_el(line, line.text, 0)? {
+        (el, "") => Ok(el),
+        (_, rest) => Err(line.error(first_char(rest), "end of line")),
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<(El, El)>, ParseError> {
+    let mut lines = Line::all(DAY, input);
+    let mut pairs = Vec::new();
+    loop {
+        let l1 = parse_packet(input, lines.next())?;
+        let l2 = parse_packet(input, lines.next())?;
+        pairs.push((l1, l2));
+
+        match lines.next() {
+            None => break,
+            Some(line) if line.text.is_empty() => {}
+            Some(line) => return Err(line.error(line.text, "an empty line between pairs")),
+        }
+    }
+    Ok(pairs)
+}
+
+pub fn part1(pairs: &[(El, El)]) -> usize {
+    pairs.iter().enumerate().filter(|(_, (l1, l2))| l1 < l2).map(|(i, _)| i + 1).sum()
+}
+
+/// The dividers sort in after every packet smaller than them, and the first divider before the
+/// second.
+pub fn part2(pairs: &[(El, El)]) -> usize {
+    let divider1 = list!(list!(Num(2)));
+    let divider2 = list!(list!(Num(6)));
+    let packets = pairs.iter().flat_map(|(l1, l2)| [l1, l2]);
+    let index1 = 1 + packets.clone().filter(|&p| *p < divider1).count();
+    let index2 = 2 + packets.filter(|&p| *p < divider2).count();
+    index1 * index2
+}
+
+pub fn solve(input: &str) -> Result<Answers<usize>, ParseError> {
+    Ok(Answers::solve(parse(input)?.as_slice(), part1, part2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_display() {
+        let input = "[1,[2,[]],10]\n[[4],4]\n";
+        let pairs = parse(input).unwrap();
+        assert_eq!(format!("{}\n{}\n", pairs[0].0, pairs[0].1), input);
+    }
+
+    #[test]
+    fn test_depth() {
+        let nested = |depth| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        let input = format!("{}\n[]\n", nested(MAX_DEPTH));
+        assert_eq!(parse(&input).unwrap()[0].0.to_string(), nested(MAX_DEPTH));
+        let err = parse(&format!("{}\n[]\n", nested(100_000))).unwrap_err();
+        assert_eq!((err.line, err.column), (1, MAX_DEPTH + 1));
+        assert_eq!(err.expected, format!("at most {MAX_DEPTH} nested lists"));
+    }
+
+    /// Small numbers and short lists, so that equal and nested-equal packets come up often.
+    fn el() -> impl Strategy<Value = El> {
+        (0..4u32)
+            .prop_map(Num)
+            .prop_recursive(4, 32, 3, |inner| prop::collection::vec(inner, 0..3).prop_map(List))
+    }
+
+    proptest! {
+        #[test]
+        fn prop_reflexive(a in el()) {
+            prop_assert_eq!(a.cmp(&a), Ordering::Equal);
+        }
+
+        #[test]
+        fn prop_antisymmetric(a in el(), b in el()) {
+            prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+            prop_assert_eq!(a == b, a.cmp(&b) == Ordering::Equal);
+            prop_assert_eq!(a.partial_cmp(&b), Some(a.cmp(&b)));
+        }
+
+        #[test]
+        fn prop_transitive(a in el(), b in el(), c in el()) {
+            if a <= b && b <= c {
+                prop_assert!(a <= c, "{} <= {} <= {} but not {} <= {}", a, b, c, a, c);
+            }
+            if a == b && b == c {
+                prop_assert_eq!(&a, &c);
+            }
+        }
+
+        #[test]
+        fn prop_sort_is_ordered(mut packets in prop::collection::vec(el(), 0..20)) {
+            packets.sort();
+            for (i, a) in packets.iter().enumerate() {
+                for b in &packets[i + 1..] {
+                    prop_assert!(a <= b, "{} sorted before {}", a, b);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use aoc_common::animate::{draw, Animation};
+use aoc_common::picture::{Picture, Rgb};
+use aoc_common::{Answers, BBox2, Dir8, Line, ParseError, Point2, Violation};
+use std::collections::HashSet;
+
+const DAY: &str = "day14";
+
+pub type Coord = Point2<i32>;
+
+#[derive(Debug, Clone)]
+pub struct World {
+    /// Positions blocked by rock (or sand at rest).
+    pub occ: HashSet<Coord>,
+    /// The lowest rock; anything below it falls forever.
+    pub void: i32,
+}
+
+/// The largest coordinate, far beyond any cave of the puzzle, which keeps the rock of a line to
+/// a sane size.
+pub const MAX_COORD: i32 = 100_000;
+
+fn parse_point(line: Line, s: &str) -> Result<Coord, ParseError> {
+    let (x, y) = s.split_once(',').ok_or_else(|| line.error(s, "a point like `498,4`"))?;
+    let coord = |c: &str| match line.parse(c, "a number")? {
+        n @ 0..=MAX_COORD => Ok(n),
+        _ => Err(line.error(c, format!("a coordinate from 0 to {MAX_COORD}"))),
+    };
+    Ok(Coord { x: coord(x)?, y: coord(y)? })
+}
+
+pub fn parse(input: &str) -> Result<World, ParseError> {
+    let mut occ = HashSet::new();
+    let mut void = 0;
+    for line in Line::all(DAY, input) {
+        let tokens: Vec<&str> = line.text.split(" -> ").collect();
+        let points: Vec<Coord> =
+            tokens.iter().map(|s| parse_point(line, s)).collect::<Result<_, _>>()?;
+
+        for (i, pair) in points.windows(2).enumerate() {
+            if pair[0].x != pair[1].x && pair[0].y != pair[1].y {
+                return Err(line.error(tokens[i + 1], "a point in line with the one before"));
+            }
+            let mut pos = pair[0];
+            loop {
+                occ.insert(pos);
+                void = void.max(pos.y);
+                if pos == pair[1] {
+                    break;
+                }
+                pos = pos.step_towards(&pair[1]);
+            }
+        }
+    }
+    Ok(World { occ, void })
+}
+
+const SOURCE: Coord = Coord { x: 500, y: 0 };
+
+/// All rock is below the source of the sand.
+pub fn validate(world: &World) -> Result<(), Violation> {
+    if let Some(rock) = world.occ.iter().filter(|p| p.y <= SOURCE.y).min_by_key(|p| (p.y, p.x)) {
+        let found = format!("rock at {},{}", rock.x, rock.y);
+        return Err(Violation::new(DAY, "rock only below the source of the sand", found));
+    }
+    Ok(())
+}
+
+/// Sand poured into the cave one unit at a time, until it comes to rest at the source, or
+/// (without a floor) until it falls into the void.
+pub struct Pour {
+    rock: World,
+    world: World,
+    floor: bool,
+    /// Where the unit falling now went through, from the source. Following units go the same
+    /// way until the last free spot on it.
+    sand_path: Vec<Coord>,
+    at_rest: i32,
+}
+
+impl Pour {
+    pub fn new(world: &World, floor: bool) -> Pour {
+        Pour {
+            rock: world.clone(),
+            world: world.clone(),
+            floor,
+            sand_path: vec![SOURCE],
+            at_rest: 0,
+        }
+    }
+
+    fn bounds(&self) -> BBox2<i32> {
+        let mut bounds =
+            BBox2::from_points(&self.world.occ).unwrap_or(BBox2 { min: SOURCE, max: SOURCE });
+        bounds = bounds.including(&SOURCE).expand(1);
+        if self.floor {
+            bounds.max.y = self.rock.void + 2;
+        }
+        bounds
+    }
+
+    /// What is at `p`, as an index into `KINDS`.
+    fn kind(&self, p: Coord) -> usize {
+        if self.rock.occ.contains(&p) || (self.floor && p.y == self.rock.void + 2) {
+            1
+        } else if self.world.occ.contains(&p) {
+            2
+        } else if p == SOURCE {
+            4
+        } else if self.sand_path.contains(&p) {
+            3
+        } else {
+            0
+        }
+    }
+
+    /// Lets a unit of sand fall until it comes to rest. Returns `false` once sand no longer
+    /// comes to rest.
+    fn drop_unit(&mut self) -> bool {
+        while let Some(pos) = self.sand_path.last() {
+            if pos.y > self.world.void && !self.floor {
+                return false;
+            }
+            if pos.y > self.world.void + 1 {
+                // Ad-hoc floor.
+                self.world.occ.insert(self.sand_path.pop().unwrap());
+                continue;
+            }
+            match [Dir8::S, Dir8::SW, Dir8::SE]
+                .iter()
+                .map(|d| *pos + d.delta())
+                .find(|p| !self.world.occ.contains(p))
+            {
+                Some(next) => self.sand_path.push(next),
+                None => {
+                    self.world.occ.insert(self.sand_path.pop().unwrap());
+                    self.at_rest += 1;
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
+/// How air, rock, sand at rest, the way the next unit falls and the source are drawn, as text
+/// and in pictures.
+const KINDS: [(char, Rgb); 5] = [
+    ('.', [20, 20, 40]),
+    ('#', [120, 110, 100]),
+    ('o', [230, 190, 90]),
+    ('~', [250, 230, 170]),
+    ('+', [255, 255, 255]),
+];
+
+impl Animation for Pour {
+    /// Rock as `#`, sand at rest as `o` and the way the next unit falls as `~`.
+    fn frame(&self) -> String { draw(&self.bounds(), |p| KINDS[self.kind(p)].0) }
+
+    fn step(&mut self) -> bool { self.drop_unit() }
+
+    fn picture(&self) -> Option<Picture> {
+        let palette = KINDS.iter().map(|(_, rgb)| *rgb).collect();
+        Some(Picture::draw(&self.bounds(), palette, |p| self.kind(p) as u8))
+    }
+}
+
+/// Number of units of sand at rest when it stops coming to rest.
+fn pour(world: &World, floor: bool) -> i32 {
+    let mut pour = Pour::new(world, floor);
+    while pour.drop_unit() {}
+    pour.at_rest
+}
+
+pub fn part1(world: &World) -> i32 { pour(world, false) }
+
+pub fn part2(world: &World) -> i32 { pour(world, true) }
+
+/// The sand of part 2, piling up on the floor.
+pub fn animation(world: &World) -> Pour { Pour::new(world, true) }
+
+/// The pile of sand at the end of part 2.
+pub fn picture(world: &World) -> Picture {
+    let mut pour = animation(world);
+    while pour.step() {}
+    pour.picture().unwrap()
+}
+
+pub fn solve(input: &str) -> Result<Answers<i32>, ParseError> {
+    Ok(Answers::solve(&parse(input)?, part1, part2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pour_frame() {
+        let world = parse("498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n").unwrap();
+        let mut pour = Pour::new(&world, false);
+        for _ in 0..5 {
+            assert!(pour.step());
+        }
+        let frame = "............\n\
+            .......+....\n\
+            .......~....\n\
+            .......~....\n\
+            .......~....\n\
+            .....#.~.##.\n\
+            .....#.~.#..\n\
+            ...###.~.#..\n\
+            ......~o.#..\n\
+            .....oooo#..\n\
+            .#########..\n\
+            ............\n";
+        assert_eq!(pour.frame(), frame);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse("-2000000000,0 -> 2000000000,0\n").unwrap_err();
+        assert_eq!((err.column, err.found.as_deref()), (1, Some("-2000000000")));
+        let err = parse("0,0 -> 300000000,0\n").unwrap_err();
+        assert_eq!((err.column, err.found.as_deref()), (8, Some("300000000")));
+        let err = parse("0,0 -> 5,5\n").unwrap_err();
+        assert_eq!((err.column, err.found.as_deref()), (8, Some("5,5")));
+        assert_eq!(parse("0,0 -> 0,100000\n").unwrap().void, 100_000);
+    }
+
+    #[test]
+    fn test_step_towards() {
+        let c = Coord { x: 10, y: 10 };
+        assert_eq!(c.step_towards(&Coord { x: 20, y: 10 }), Coord { x: 11, y: 10 });
+        assert_eq!(c.step_towards(&Coord { x: 2, y: 10 }), Coord { x: 9, y: 10 });
+        assert_eq!(c.step_towards(&Coord { x: 10, y: 1 }), Coord { x: 10, y: 9 });
+        assert_eq!(c.step_towards(&Coord { x: 10, y: 100 }), Coord { x: 10, y: 11 });
+    }
+}
@@ -0,0 +1,283 @@
+use aoc_common::{params, Answers, Line, ParseError, Point2};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::{BTreeMap, HashSet};
+use std::ops::Range;
+
+const DAY: &str = "day15";
+
+pub type Pos = Point2<i32>;
+
+/// A set of values stored as disjoint half-open ranges, kept sorted by start.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeSet<T> {
+    ranges: BTreeMap<T, Range<T>>,
+}
+
+impl<T: Ord + Copy> RangeSet<T> {
+    pub fn new() -> RangeSet<T> { RangeSet { ranges: BTreeMap::new() } }
+
+    /// Adds a range, merging it with any ranges it overlaps or touches. Empty ranges are ignored.
+    pub fn add(&mut self, mut new_range: Range<T>) {
+        if new_range.start >= new_range.end {
+            return;
+        }
+        let mut to_replace = Vec::new();
+        for (s, r) in self.ranges.iter() {
+            if to_replace.is_empty() && new_range.start < *s && new_range.end < *s {
+                self.ranges.insert(new_range.start, new_range);
+                return;
+            } else if overlaps(&new_range, r) {
+                new_range = (new_range.start.min(r.start))..(new_range.end.max(r.end));
+                to_replace.push(*s);
+            }
+        }
+        for s in &to_replace {
+            self.ranges.remove(s);
+        }
+        self.ranges.insert(new_range.start, new_range);
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        for (start, r) in &self.ranges {
+            if x < *start {
+                continue;
+            }
+            if r.contains(&x) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// The part of the set that lies within `range`.
+    pub fn clamp(&self, range: Range<T>) -> RangeSet<T> {
+        let mut result = RangeSet::new();
+        for r in self.ranges.values() {
+            result.add(r.start.clamp(range.start, range.end)..r.end.clamp(range.start, range.end));
+        }
+        result
+    }
+
+    /// The disjoint ranges making up the set, in order.
+    pub fn ranges(&self) -> impl Iterator<Item = &Range<T>> { self.ranges.values() }
+}
+
+impl<T: Ord + Copy> Default for RangeSet<T> {
+    fn default() -> RangeSet<T> { RangeSet::new() }
+}
+
+fn overlaps<T: PartialOrd>(a: &Range<T>, b: &Range<T>) -> bool {
+    a.start <= b.end && a.end >= b.start
+}
+
+fn parse_line(line: Line) -> Result<(Pos, Pos), ParseError> {
+    lazy_static! {
+        static ref LINE_RE: Regex = Regex::new(r"Sensor at x=(?P<sx>-?\d+), y=(?P<sy>-?\d+): closest beacon is at x=(?P<bx>-?\d+), y=(?P<by>-?\d+)").unwrap();
+    }
+    let expected = "`Sensor at x=<x>, y=<y>: closest beacon is at x=<x>, y=<y>`";
+    let captures = LINE_RE.captures(line.text).ok_or_else(|| line.error(line.text, expected))?;
+    let coord = |name| line.parse(captures.name(name).unwrap().as_str(), "a coordinate");
+    let sensor = Pos { x: coord("sx")?, y: coord("sy")? };
+    let beacon = Pos { x: coord("bx")?, y: coord("by")? };
+    Ok((sensor, beacon))
+}
+
+params! {
+    pub struct Params {
+        /// Row checked in part 1.
+        row: i32 = 2000000,
+        /// Side of the square searched for the distress beacon in part 2.
+        size: i32 = 4000000,
+    }
+    sample { row: 10, size: 20 }
+}
+
+/// Each sensor with the closest beacon it detects.
+pub fn parse(input: &str) -> Result<Vec<(Pos, Pos)>, ParseError> {
+    Line::all(DAY, input).map(parse_line).collect()
+}
+
+/// The x ranges at row `y` covered by any of the sensors.
+fn coverage(sensors: &[(Pos, Pos)], y: i32) -> RangeSet<i32> {
+    let mut occupation = RangeSet::new();
+    for (sensor, beacon) in sensors {
+        let range = sensor.manhattan(beacon);
+        let distance_to_row = (y - sensor.y).abs();
+        let half_width = (range - distance_to_row).abs();
+        if distance_to_row > range {
+            continue;
+        }
+        occupation.add((sensor.x - half_width)..(1 + (sensor.x + half_width)));
+    }
+    occupation
+}
+
+pub fn part1(sensors: &[(Pos, Pos)], params: &Params) -> usize {
+    let step1_y = params.row;
+    let beacons_at_step1_y: HashSet<_> =
+        sensors.iter().map(|(_, beacon)| beacon).filter(|beacon| beacon.y == step1_y).collect();
+    coverage(sensors, step1_y).ranges().map(|r| r.len()).sum::<usize>() - beacons_at_step1_y.len()
+}
+
+/// The tuning frequency of the one spot in the area no sensor covers, or `None` if there is none,
+/// which the puzzle rules out but another `size` may not.
+pub fn part2(sensors: &[(Pos, Pos)], params: &Params) -> Option<i64> {
+    let size = params.size;
+    (0..size).find_map(|y| {
+        let clamped = coverage(sensors, y).clamp(0..size);
+        (clamped.ranges.len() > 1).then(|| clamped.ranges[&0].end as i64 * 4000000 + y as i64)
+    })
+}
+
+pub fn solve(input: &str, params: &Params) -> Result<Answers<usize, Option<i64>>, ParseError> {
+    Ok(Answers::solve(parse(input)?.as_slice(), |s| part1(s, params), |s| part2(s, params)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_overlap() {
+        assert!(overlaps(&(5..10), &(3..15)));
+        assert!(overlaps(&(5..10), &(10..15)));
+        assert!(overlaps(&(5..10), &(5..10)));
+        assert!(overlaps(&(10..15), &(5..10)));
+        assert!(overlaps(&(10..15), &(5..10)));
+        assert!(overlaps(&(5..20), &(10..15)));
+
+        assert!(!overlaps(&(5..10), &(11..15)));
+        assert!(!overlaps(&(10..20), &(3..9)));
+    }
+
+    #[test]
+    fn add_range_before() {
+        let mut r = RangeSet::new();
+        r.add(10..20);
+        r.add(30..40);
+
+        r.add(1..5);
+
+        assert_eq!(3, r.ranges.len());
+    }
+
+    #[test]
+    fn add_range_touching() {
+        let mut r = RangeSet::new();
+        r.add(10..20);
+        r.add(30..40);
+
+        r.add(5..10);
+
+        assert_eq!(2, r.ranges.len());
+        assert_eq!(*r.ranges.iter().next().unwrap().1, 5..20);
+
+        r.add(20..25);
+        assert_eq!(*r.ranges.iter().next().unwrap().1, 5..25);
+    }
+
+    #[test]
+    fn add_range_overlap() {
+        let mut r = RangeSet::new();
+        r.add(10..20);
+        r.add(30..40);
+
+        r.add(5..15);
+
+        assert_eq!(2, r.ranges.len());
+        assert_eq!(*r.ranges.iter().next().unwrap().1, 5..20);
+
+        r.add(10..15);
+
+        assert_eq!(2, r.ranges.len());
+        assert_eq!(*r.ranges.iter().next().unwrap().1, 5..20);
+
+        r.add(15..25);
+
+        assert_eq!(2, r.ranges.len());
+        assert_eq!(*r.ranges.iter().next().unwrap().1, 5..25);
+    }
+
+    #[test]
+    fn add_range_joining() {
+        let mut r = RangeSet::new();
+        r.add(10..20);
+        r.add(30..40);
+
+        r.add(20..30);
+
+        assert_eq!(1, r.ranges.len());
+        assert_eq!(*r.ranges.iter().next().unwrap().1, 10..40);
+    }
+
+    const UNIVERSE: i32 = 64;
+
+    fn bitset(ranges: &[Range<i32>]) -> Vec<bool> {
+        let mut bits = vec![false; UNIVERSE as usize];
+        for r in ranges {
+            for x in r.clone() {
+                bits[x as usize] = true;
+            }
+        }
+        bits
+    }
+
+    fn assert_canonical(set: &RangeSet<i32>) -> Result<(), TestCaseError> {
+        for (start, r) in &set.ranges {
+            prop_assert_eq!(*start, r.start);
+            prop_assert!(r.start < r.end, "empty range {:?}", r);
+        }
+        let ranges: Vec<_> = set.ranges().collect();
+        for pair in ranges.windows(2) {
+            prop_assert!(
+                pair[0].end < pair[1].start,
+                "{:?} and {:?} should be merged",
+                pair[0],
+                pair[1]
+            );
+        }
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_bitset(ranges in prop::collection::vec((0..UNIVERSE, 0..UNIVERSE), 0..20)) {
+            let ranges: Vec<Range<i32>> =
+                ranges.into_iter().map(|(a, b)| a.min(b)..a.max(b)).collect();
+            let mut set = RangeSet::new();
+            for r in &ranges {
+                set.add(r.clone());
+            }
+            assert_canonical(&set)?;
+            let bits = bitset(&ranges);
+            for x in 0..UNIVERSE {
+                prop_assert_eq!(set.contains(x), bits[x as usize], "x = {}", x);
+            }
+            let len: usize = set.ranges().map(|r| r.len()).sum();
+            prop_assert_eq!(len, bits.iter().filter(|&&b| b).count());
+        }
+
+        #[test]
+        fn prop_clamp_matches_bitset(
+            ranges in prop::collection::vec((0..UNIVERSE, 0..UNIVERSE), 0..20),
+            (lo, hi) in (0..UNIVERSE, 0..UNIVERSE),
+        ) {
+            let ranges: Vec<Range<i32>> =
+                ranges.into_iter().map(|(a, b)| a.min(b)..a.max(b)).collect();
+            let bounds = lo.min(hi)..lo.max(hi);
+            let mut set = RangeSet::new();
+            for r in &ranges {
+                set.add(r.clone());
+            }
+            let clamped = set.clamp(bounds.clone());
+            assert_canonical(&clamped)?;
+            let bits = bitset(&ranges);
+            for x in 0..UNIVERSE {
+                let expected = bits[x as usize] && bounds.contains(&x);
+                prop_assert_eq!(clamped.contains(x), expected, "x = {}", x);
+            }
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use aoc_common::parse::end_of_input;
+use aoc_common::search::bfs;
+use aoc_common::{event, params, Answers, Line, ParseError, Violation};
+use itertools::Itertools;
+use regex::Regex;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+const DAY: &str = "day16";
+
+#[derive(Debug)]
+pub struct Valve {
+    pub rate: i32,
+    pub tunnels: Vec<String>,
+}
+
+/// All valves, keyed by name.
+pub type System = HashMap<String, Valve>;
+
+#[derive(Clone)]
+struct State {
+    position: String,
+    visited: HashSet<String>,
+    time_left: i32,
+    pressure: i32,
+}
+
+impl State {
+    fn new(time: i32, pos: String) -> State {
+        State {
+            position: pos.clone(),
+            visited: HashSet::from_iter(vec![pos]),
+            time_left: time,
+            pressure: 0,
+        }
+    }
+
+    fn next(&self, new_pos: &str, dist: i32, rate: i32) -> Option<State> {
+        if self.visited.contains(new_pos) {
+            return None;
+        }
+        let time_left = self.time_left - dist - 1;
+        if time_left < 0 {
+            return None;
+        }
+
+        let new_pos = new_pos.to_string();
+
+        let mut new_state = (*self).clone();
+        new_state.position = new_pos.clone();
+        new_state.visited.insert(new_pos);
+        new_state.time_left = time_left;
+        new_state.pressure += time_left * rate;
+        Some(new_state)
+    }
+}
+
+fn distance(system: &System, from: &str, to: &str) -> i32 {
+    let tunnels = |valve: &&str| system[*valve].tunnels.iter().map(|next| next.as_str());
+    bfs(from, tunnels, |valve| *valve == to).map_or(i32::MAX, |found| found.dist as i32)
+}
+
+fn most_pressure(
+    time: i32,
+    dist: &HashMap<&str, Vec<(&str, i32)>>,
+    valves: &HashMap<&str, &Valve>,
+    allowed: &HashSet<&str>,
+) -> i32 {
+    let mut result = 0;
+    let mut todo = VecDeque::new();
+    todo.push_back(State::new(time, String::from("AA")));
+    while let Some(state) = todo.pop_front() {
+        result = result.max(state.pressure);
+        for (next, dist) in &dist[&*state.position] {
+            if !allowed.contains(next) {
+                continue;
+            }
+            if let Some(new_state) = state.next(next, *dist, valves[next].rate) {
+                todo.push_back(new_state);
+            }
+        }
+    }
+    result
+}
+
+fn fprint(valves: &HashSet<&str>) -> String { valves.iter().sorted().join("") }
+
+type Distances<'a> = HashMap<&'a str, Vec<(&'a str, i32)>>;
+
+pub fn parse(input: &str) -> Result<System, ParseError> {
+    let valve_re = Regex::new(r"Valve (?P<valve>\w\w) has flow rate=(?P<rate>\d+); tunnels? leads? to valves? (?P<tunnels>.*)").unwrap();
+    let mut world = HashMap::new();
+
+    let mut tunnels = Vec::new();
+
+    for line in Line::all(DAY, input) {
+        let expected = "`Valve <name> has flow rate=<n>; tunnels lead to valves <names>`";
+        let cap = valve_re.captures(line.text).ok_or_else(|| line.error(line.text, expected))?;
+        let to: Vec<&str> = cap.name("tunnels").unwrap().as_str().split(", ").collect();
+        tunnels.extend(to.iter().map(|name| (line, *name)));
+        world.insert(
+            cap.name("valve").unwrap().as_str().to_string(),
+            Valve {
+                rate: line.parse(cap.name("rate").unwrap().as_str(), "a flow rate")?,
+                tunnels: to.into_iter().map(|s| s.to_string()).collect(),
+            },
+        );
+    }
+
+    // The search follows tunnels blindly, so they must all lead somewhere.
+    if let Some((line, name)) = tunnels.into_iter().find(|(_, name)| !world.contains_key(*name)) {
+        return Err(line.error(name, "the name of a valve"));
+    }
+    if !world.contains_key("AA") {
+        return Err(end_of_input(DAY, input, "the starting valve `AA`"));
+    }
+    Ok(world)
+}
+
+/// The valves worth opening (and the start), with the distances between them.
+fn useful_valves(world: &System) -> (HashMap<&str, &Valve>, Distances<'_>) {
+    let valves: HashMap<&str, &Valve> = world
+        .iter()
+        .filter(|(name, valve)| *name == "AA" || valve.rate > 0)
+        .map(|(name, value)| (&name[..], value))
+        .collect();
+    let mut dist = HashMap::new();
+    for from in valves.keys() {
+        for to in valves.keys() {
+            if from == to {
+                continue;
+            }
+            let d = distance(world, from, to);
+            dist.entry(*from).or_insert(Vec::new()).push((*to, d));
+        }
+    }
+    (valves, dist)
+}
+
+params! {
+    pub struct Params {
+        /// Minutes before the volcano erupts.
+        minutes: i32 = 30,
+        /// Minutes spent teaching the elephant in part 2.
+        teaching: i32 = 4,
+    }
+}
+
+/// Valves worth opening part 2 splits between the two of us, every one in every way.
+const MAX_USEFUL_VALVES: usize = 16;
+
+/// Few enough valves worth opening to try every split, all of them reachable from the start, and
+/// time left after teaching the elephant.
+pub fn validate(world: &System, params: &Params) -> Result<(), Violation> {
+    let useful: Vec<&str> =
+        world.iter().filter(|(_, valve)| valve.rate > 0).map(|(name, _)| name.as_str()).collect();
+    if useful.len() > MAX_USEFUL_VALVES {
+        let assumption = format!("at most {MAX_USEFUL_VALVES} valves with flow");
+        return Err(Violation::new(DAY, assumption, useful.len().to_string()));
+    }
+    let unreachable = useful.iter().sorted().find(|valve| distance(world, "AA", valve) == i32::MAX);
+    if let Some(valve) = unreachable {
+        let found = format!("no way to `{valve}`");
+        return Err(Violation::new(DAY, "valves with flow reachable from `AA`", found));
+    }
+    if params.teaching >= params.minutes {
+        let found = format!("{} minutes of teaching out of {}", params.teaching, params.minutes);
+        return Err(Violation::new(DAY, "time left after teaching the elephant", found));
+    }
+    Ok(())
+}
+
+pub fn part1(world: &System, params: &Params) -> i32 {
+    let (valves, dist) = useful_valves(world);
+    let all_valves = valves.keys().copied().collect();
+    most_pressure(params.minutes, &dist, &valves, &all_valves)
+}
+
+/// How often part 2 reports the subsets of valves it went through.
+const SUBSETS_PER_EVENT: usize = 4096;
+
+pub fn part2(world: &System, params: &Params) -> i32 {
+    let minutes = params.minutes - params.teaching;
+    let (valves, dist) = useful_valves(world);
+    let all_valves: HashSet<&str> = valves.keys().copied().collect();
+
+    let mut results = HashMap::new();
+    let mut complements = Vec::new();
+    let subsets = 1usize << valves.len();
+    for (i, s) in valves.keys().powerset().enumerate() {
+        if i.is_multiple_of(SUBSETS_PER_EVENT) {
+            event!("subsets", done = i, total = subsets);
+        }
+        let s: HashSet<&str> = s.into_iter().copied().collect();
+        let fp = fprint(&s);
+        results.insert(fp.clone(), most_pressure(minutes, &dist, &valves, &s));
+
+        let compl = all_valves.difference(&s).copied().collect();
+        complements.push((fp, fprint(&compl)));
+    }
+
+    let mut best = 0;
+    for (human, elephant) in complements {
+        best = best.max(results[&human] + results[&elephant]);
+    }
+    best
+}
+
+pub fn solve(input: &str, params: &Params) -> Result<Answers<i32>, ParseError> {
+    Ok(Answers::solve(&parse(input)?, |w| part1(w, params), |w| part2(w, params)))
+}
@@ -0,0 +1,263 @@
+use aoc_common::animate::Animation;
+use aoc_common::cycle::find_cycle;
+use aoc_common::parse::end_of_input;
+use aoc_common::{event, params, Answers, Dir4, Line, ParseError, Point2};
+use std::collections::HashSet;
+
+const DAY: &str = "day17";
+
+pub type Pos = Point2<i64>;
+
+struct Block {
+    pieces: Vec<Pos>,
+    width: i64,
+}
+
+fn collides(world: &HashSet<Pos>, block: &Block, offset: &Pos) -> bool {
+    offset.x < 0
+        || offset.x + block.width > 7
+        || offset.y < 0
+        || block.pieces.iter().map(|p| *p + *offset).any(|p| world.contains(&p))
+}
+
+fn gc(world: &mut HashSet<Pos>, height: i64) { world.retain(|x| x.y > (height - 100i64)) }
+
+/// The push of each jet of hot gas, in order.
+pub fn parse(input: &str) -> Result<Vec<Pos>, ParseError> {
+    let line = Line::all(DAY, input)
+        .next()
+        .filter(|line| !line.text.is_empty())
+        .ok_or_else(|| end_of_input(DAY, input, "a line of jets"))?;
+    line.text
+        .chars()
+        .enumerate()
+        .map(|(i, c)| match c {
+            '<' => Ok(Dir4::W.delta()),
+            '>' => Ok(Dir4::E.delta()),
+            _ => Err(line.error_at_char(i, "`<` or `>`")),
+        })
+        .collect()
+}
+
+/// The shapes of the rocks, in the order they fall.
+fn blocks() -> [Block; 5] {
+    [
+        Block {
+            pieces: vec![
+                Pos { x: 0, y: 0 },
+                Pos { x: 1, y: 0 },
+                Pos { x: 2, y: 0 },
+                Pos { x: 3, y: 0 },
+            ],
+            width: 4,
+        },
+        Block {
+            pieces: vec![
+                Pos { x: 0, y: 1 },
+                Pos { x: 1, y: 0 },
+                Pos { x: 1, y: 1 },
+                Pos { x: 1, y: 2 },
+                Pos { x: 2, y: 1 },
+            ],
+            width: 3,
+        },
+        Block {
+            pieces: vec![
+                Pos { x: 0, y: 0 },
+                Pos { x: 1, y: 0 },
+                Pos { x: 2, y: 0 },
+                Pos { x: 2, y: 1 },
+                Pos { x: 2, y: 2 },
+            ],
+            width: 3,
+        },
+        Block {
+            pieces: vec![
+                Pos { x: 0, y: 0 },
+                Pos { x: 0, y: 1 },
+                Pos { x: 0, y: 2 },
+                Pos { x: 0, y: 3 },
+            ],
+            width: 1,
+        },
+        Block {
+            pieces: vec![
+                Pos { x: 0, y: 0 },
+                Pos { x: 0, y: 1 },
+                Pos { x: 1, y: 0 },
+                Pos { x: 1, y: 1 },
+            ],
+            width: 2,
+        },
+    ]
+}
+
+/// Rows at the top of the tower kept in the state of a `Tower`, assuming no rock falls deeper.
+const SURFACE_ROWS: i64 = 32;
+
+/// A tower growing as rocks fall onto it.
+struct Tower {
+    blocks: [Block; 5],
+    jets: Vec<Pos>,
+    next_block: usize,
+    next_jet: usize,
+    dropped: usize,
+    /// Where the last block came to rest.
+    last: Pos,
+    world: HashSet<Pos>,
+    height: i64,
+}
+
+impl Tower {
+    fn new(jets: &[Pos]) -> Tower {
+        Tower {
+            blocks: blocks(),
+            jets: jets.to_vec(),
+            next_block: 0,
+            next_jet: 0,
+            dropped: 0,
+            last: Pos::origin(),
+            world: HashSet::new(),
+            height: 0,
+        }
+    }
+
+    fn drop_block(&mut self) {
+        if self.dropped.is_multiple_of(1024 * 1024) {
+            gc(&mut self.world, self.height);
+        }
+        let block = &self.blocks[self.next_block];
+        self.next_block = (self.next_block + 1) % self.blocks.len();
+        self.dropped += 1;
+
+        let fall = Pos { x: 0, y: -1 };
+        let mut block_pos = Pos { x: 2, y: self.height + 3 };
+        loop {
+            let after_jet = block_pos + self.jets[self.next_jet];
+            self.next_jet = (self.next_jet + 1) % self.jets.len();
+
+            if !collides(&self.world, block, &after_jet) {
+                block_pos = after_jet;
+            }
+
+            let after_fall = block_pos + fall;
+            if !collides(&self.world, block, &after_fall) {
+                block_pos = after_fall;
+            } else {
+                for p in block.pieces.iter().map(|p| *p + block_pos) {
+                    self.world.insert(p);
+                    self.height = self.height.max(p.y + 1);
+                }
+                self.last = block_pos;
+                break;
+            }
+        }
+    }
+
+    /// Everything deciding how the tower grows from here: the next block and jet, and the shape
+    /// of the top of the tower, one bit per column (the floor counting as full rows).
+    fn state(&self) -> (usize, usize, Vec<u8>) {
+        let surface = (self.height - SURFACE_ROWS..self.height)
+            .map(|y| {
+                (0..7)
+                    .filter(|&x| y < 0 || self.world.contains(&Pos { x, y }))
+                    .fold(0, |row, x| row | 1 << x)
+            })
+            .collect();
+        (self.next_block, self.next_jet, surface)
+    }
+}
+
+/// How often a tower reports the rocks dropped while no cycle is found.
+const ROCKS_PER_EVENT: usize = 100000;
+
+/// Height of the tower after `limit` blocks have fallen, pushed around by `jets`. Once the
+/// tower starts repeating, the rest of the height is extrapolated.
+fn tower_height(jets: &[Pos], limit: usize) -> i64 {
+    let mut tower = Tower::new(jets);
+    let mut heights = vec![0];
+    let states = std::iter::once(tower.state()).chain((0..limit).map(|n| {
+        if n > 0 && n.is_multiple_of(ROCKS_PER_EVENT) {
+            event!("rocks", dropped = n, height = tower.height);
+        }
+        tower.drop_block();
+        heights.push(tower.height);
+        tower.state()
+    }));
+    match find_cycle(states) {
+        Some(cycle) => {
+            event!("cycle", start = cycle.start, period = cycle.period);
+            cycle.extrapolate(&heights, limit)
+        }
+        None => heights[limit],
+    }
+}
+
+/// Rows of the tower shown in a frame.
+const VIEW_ROWS: i64 = 24;
+
+params! {
+    pub struct Params {
+        /// Rocks falling in part 1.
+        rocks: usize = 2022,
+        /// Rocks falling in part 2.
+        many_rocks: usize = 1000000000000,
+    }
+}
+
+/// The rocks of part 1 falling, a rock per step.
+pub struct Fall {
+    tower: Tower,
+    limit: usize,
+}
+
+impl Animation for Fall {
+    /// The top of the tower, with the rock that fell last as `@`.
+    fn frame(&self) -> String {
+        let tower = &self.tower;
+        let last_block = (tower.next_block + tower.blocks.len() - 1) % tower.blocks.len();
+        let last: Vec<Pos> = match tower.dropped {
+            0 => Vec::new(),
+            _ => tower.blocks[last_block].pieces.iter().map(|p| *p + tower.last).collect(),
+        };
+        let mut out = String::new();
+        for y in (0.max(tower.height - VIEW_ROWS)..tower.height).rev() {
+            out.push('|');
+            for x in 0..7 {
+                let p = Pos { x, y };
+                out.push(if last.contains(&p) {
+                    '@'
+                } else if tower.world.contains(&p) {
+                    '#'
+                } else {
+                    '.'
+                });
+            }
+            out.push_str("|\n");
+        }
+        if tower.height <= VIEW_ROWS {
+            out.push_str("+-------+\n");
+        }
+        out
+    }
+
+    fn step(&mut self) -> bool {
+        if self.tower.dropped == self.limit {
+            return false;
+        }
+        self.tower.drop_block();
+        true
+    }
+}
+
+pub fn animation(jets: &[Pos], params: &Params) -> Fall {
+    Fall { tower: Tower::new(jets), limit: params.rocks }
+}
+
+pub fn part1(jets: &[Pos], params: &Params) -> i64 { tower_height(jets, params.rocks) }
+
+pub fn part2(jets: &[Pos], params: &Params) -> i64 { tower_height(jets, params.many_rocks) }
+
+pub fn solve(input: &str, params: &Params) -> Result<Answers<i64>, ParseError> {
+    Ok(Answers::solve(parse(input)?.as_slice(), |j| part1(j, params), |j| part2(j, params)))
+}
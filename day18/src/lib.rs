@@ -0,0 +1,48 @@
+use aoc_common::parse::end_of_input;
+use aoc_common::search::flood;
+use aoc_common::{Answers, BBox3, Line, ParseError, Point3};
+use std::collections::HashSet;
+
+const DAY: &str = "day18";
+
+pub type Pos = Point3<i32>;
+
+/// The cubes of lava.
+pub fn parse(input: &str) -> Result<HashSet<Pos>, ParseError> {
+    let mut lava = HashSet::new();
+    for line in Line::all(DAY, input) {
+        match line.text.split(',').collect::<Vec<_>>()[..] {
+            [x, y, z] => {
+                lava.insert(Pos {
+                    x: line.parse(x, "a coordinate")?,
+                    y: line.parse(y, "a coordinate")?,
+                    z: line.parse(z, "a coordinate")?,
+                });
+            }
+            _ => return Err(line.error(line.text, "a cube like `2,2,2`")),
+        }
+    }
+    if lava.is_empty() {
+        return Err(end_of_input(DAY, input, "a cube"));
+    }
+    Ok(lava)
+}
+
+pub fn part1(lava: &HashSet<Pos>) -> usize {
+    lava.iter().map(|p| p.neighbours6().filter(|p| !lava.contains(p)).count()).sum()
+}
+
+pub fn part2(lava: &HashSet<Pos>) -> usize {
+    let bounds = BBox3::from_points(lava).unwrap();
+    let outside = bounds.expand(1);
+
+    // The air around the droplet, each cube of which touches the lava on some sides.
+    let air = flood(outside.min, |p: &Pos| {
+        p.neighbours6().filter(|p| outside.contains(p) && !lava.contains(p)).collect::<Vec<_>>()
+    });
+    air.dist.keys().map(|p| p.neighbours6().filter(|p| lava.contains(p)).count()).sum()
+}
+
+pub fn solve(input: &str) -> Result<Answers<usize>, ParseError> {
+    Ok(Answers::solve(&parse(input)?, part1, part2))
+}
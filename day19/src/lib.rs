@@ -0,0 +1,219 @@
+use aoc_common::{event, params, Answers, Line, ParseError};
+use regex::Regex;
+use std::collections::VecDeque;
+
+const DAY: &str = "day19";
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct Resources {
+    ore: i32,
+    clay: i32,
+    obs: i32,
+    geode: i32,
+
+    ore_robots: i32,
+    clay_robots: i32,
+    obs_robots: i32,
+    geode_robots: i32,
+
+    time: i32,
+}
+
+impl Resources {
+    fn new(time: i32) -> Resources {
+        Resources {
+            ore: 0,
+            clay: 0,
+            obs: 0,
+            geode: 0,
+
+            ore_robots: 1,
+            clay_robots: 0,
+            obs_robots: 0,
+            geode_robots: 0,
+
+            time,
+        }
+    }
+}
+
+/// A blueprint: what each kind of robot costs.
+#[derive(Debug)]
+pub struct Costs {
+    pub ore_cost_ore: i32,
+    pub clay_cost_ore: i32,
+    pub obs_cost_ore: i32,
+    pub obs_cost_clay: i32,
+    pub geode_cost_ore: i32,
+    pub geode_cost_obs: i32,
+}
+
+fn div_ceil(a: i32, b: i32) -> i32 { (a + b - 1) / b }
+
+/// How often a search reports the states it went through.
+const STATES_PER_EVENT: usize = 1 << 20;
+
+fn run(time: i32, costs: &Costs) -> i32 {
+    let mut max_possible_geodes = Vec::new();
+    for i in 0..(time + 1) {
+        max_possible_geodes.push(i + max_possible_geodes.last().unwrap_or(&0));
+    }
+
+    let mut max_score = 0;
+    let mut explored = 0usize;
+    let mut todo = VecDeque::new();
+    todo.push_back(Resources::new(time));
+    while let Some(r) = todo.pop_back() {
+        explored += 1;
+        if explored.is_multiple_of(STATES_PER_EVENT) {
+            event!("states", explored = explored, waiting = todo.len(), geodes = max_score);
+        }
+        if r.time < 0 {
+            continue;
+        }
+        if r.geode + r.geode_robots * r.time + max_possible_geodes[r.time as usize] < max_score {
+            continue;
+        }
+
+        // Buy ore bot
+        let wait_time = div_ceil(costs.ore_cost_ore - r.ore, r.ore_robots).max(0) + 1;
+        if wait_time < r.time {
+            todo.push_back(Resources {
+                ore: r.ore + r.ore_robots * wait_time - costs.ore_cost_ore,
+                clay: r.clay + r.clay_robots * wait_time,
+                obs: r.obs + r.obs_robots * wait_time,
+                geode: r.geode + r.geode_robots * wait_time,
+
+                ore_robots: r.ore_robots + 1,
+                time: r.time - wait_time,
+                ..r
+            });
+        }
+
+        // Buy clay bot
+        let wait_time = div_ceil(costs.clay_cost_ore - r.ore, r.ore_robots).max(0) + 1;
+        if wait_time < r.time {
+            todo.push_back(Resources {
+                ore: r.ore + r.ore_robots * wait_time - costs.clay_cost_ore,
+                clay: r.clay + r.clay_robots * wait_time,
+                obs: r.obs + r.obs_robots * wait_time,
+                geode: r.geode + r.geode_robots * wait_time,
+
+                clay_robots: r.clay_robots + 1,
+                time: r.time - wait_time,
+                ..r
+            });
+        }
+
+        // Buy obs bot
+        if r.clay_robots > 0 {
+            let wait_time = div_ceil(costs.obs_cost_ore - r.ore, r.ore_robots)
+                .max(div_ceil(costs.obs_cost_clay - r.clay, r.clay_robots))
+                .max(0)
+                + 1;
+            if wait_time < r.time {
+                todo.push_back(Resources {
+                    ore: r.ore + r.ore_robots * wait_time - costs.obs_cost_ore,
+                    clay: r.clay + r.clay_robots * wait_time - costs.obs_cost_clay,
+                    obs: r.obs + r.obs_robots * wait_time,
+                    geode: r.geode + r.geode_robots * wait_time,
+
+                    obs_robots: r.obs_robots + 1,
+                    time: r.time - wait_time,
+                    ..r
+                });
+            }
+        }
+
+        // Buy geode bot
+        if r.obs_robots > 0 {
+            let wait_time = div_ceil(costs.geode_cost_ore - r.ore, r.ore_robots)
+                .max(div_ceil(costs.geode_cost_obs - r.obs, r.obs_robots))
+                .max(0)
+                + 1;
+            if wait_time < r.time {
+                todo.push_back(Resources {
+                    ore: r.ore + r.ore_robots * wait_time - costs.geode_cost_ore,
+                    clay: r.clay + r.clay_robots * wait_time,
+                    obs: r.obs + r.obs_robots * wait_time - costs.geode_cost_obs,
+                    geode: r.geode + r.geode_robots * wait_time,
+
+                    geode_robots: r.geode_robots + 1,
+                    time: r.time - wait_time,
+                    ..r
+                });
+            }
+        }
+
+        if r.geode_robots > 0 {
+            max_score = max_score.max(r.geode + r.geode_robots * r.time);
+        }
+    }
+    event!("states", explored = explored, geodes = max_score);
+    max_score
+}
+
+pub fn parse(input: &str) -> Result<Vec<Costs>, ParseError> {
+    let blueprint_re = Regex::new(r"(?x)
+        Blueprint\s(?P<id>\d+):\s
+             Each\sore\srobot\scosts\s(?P<ore_cost>\d+)\sore.\s
+             Each\sclay\srobot\scosts\s(?P<clay_cost>\d+)\sore.\s
+             Each\sobsidian\srobot\scosts\s(?P<obs_cost_ore>\d+)\sore\sand\s(?P<obs_cost_clay>\d+)\sclay.\s
+             Each\sgeode\srobot\scosts\s(?P<geode_cost_ore>\d+)\sore\sand\s(?P<geode_cost_obs>\d+)\sobsidian.")
+        .unwrap();
+
+    let mut blueprints = Vec::new();
+
+    for line in Line::all(DAY, input) {
+        let expected = "a blueprint like `Blueprint <n>: Each ore robot costs <n> ore. ...`";
+        let cap =
+            blueprint_re.captures(line.text).ok_or_else(|| line.error(line.text, expected))?;
+        let cost = |name| line.parse(cap.name(name).unwrap().as_str(), "a cost");
+        blueprints.push(Costs {
+            ore_cost_ore: cost("ore_cost")?,
+            clay_cost_ore: cost("clay_cost")?,
+            obs_cost_ore: cost("obs_cost_ore")?,
+            obs_cost_clay: cost("obs_cost_clay")?,
+            geode_cost_ore: cost("geode_cost_ore")?,
+            geode_cost_obs: cost("geode_cost_obs")?,
+        });
+    }
+    Ok(blueprints)
+}
+
+params! {
+    pub struct Params {
+        /// Minutes to open geodes in part 1.
+        minutes: i32 = 24,
+        /// Minutes to open geodes in part 2.
+        more_minutes: i32 = 32,
+        /// Blueprints left uneaten by the elephants in part 2.
+        blueprints: usize = 3,
+    }
+}
+
+pub fn part1(blueprints: &[Costs], params: &Params) -> i32 {
+    let mut total_q = 0;
+    for (i, costs) in blueprints.iter().enumerate() {
+        let blueprint = i as i32 + 1;
+        let max_score = run(params.minutes, costs);
+        event!("blueprints", done = blueprint, total = blueprints.len(), geodes = max_score);
+        total_q += blueprint * max_score;
+    }
+    total_q
+}
+
+pub fn part2(blueprints: &[Costs], params: &Params) -> i32 {
+    let mut result = 1;
+    let total = blueprints.len().min(params.blueprints);
+    for (i, costs) in blueprints.iter().take(params.blueprints).enumerate() {
+        let score = run(params.more_minutes, costs);
+        event!("blueprints", done = i + 1, total = total, geodes = score);
+        result *= score;
+    }
+    result
+}
+
+pub fn solve(input: &str, params: &Params) -> Result<Answers<i32>, ParseError> {
+    Ok(Answers::solve(parse(input)?.as_slice(), |b| part1(b, params), |b| part2(b, params)))
+}
@@ -0,0 +1,444 @@
+use aoc_common::{params, Answers, Line, ParseError, Violation};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::str::FromStr;
+
+const DAY: &str = "day2";
+
+/// One round: the opponent's letter and ours, which the `Rules` give a meaning.
+pub type Round = (char, char);
+
+/// A shape of the game, by its place in the cycle of `Rules::shapes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shape(pub usize);
+
+/// How a round ends for us.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    const ALL: [Outcome; 3] = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+}
+
+/// How to read the second column of a round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    /// The shape we play (part 1).
+    Shape,
+    /// How the round must end (part 2).
+    Outcome,
+}
+
+/// The rules of a cyclic game like rock paper scissors, with the letters standing for its shapes
+/// and outcomes, as read from a rules file (see `rules/rps.txt`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rules {
+    /// Where the rules come from: the name of built-in ones or a path.
+    pub name: String,
+    /// In cycle order: each shape beats the `(n - 1) / 2` shapes before it, wrapping around, and
+    /// loses to the others.
+    pub shapes: Vec<String>,
+    /// Points for playing each shape.
+    pub points: Vec<i32>,
+    pub opponent: Vec<char>,
+    pub player: Vec<char>,
+    /// Letters of losing, a draw and winning.
+    pub outcomes: [char; 3],
+    /// Points for losing, a draw and winning.
+    pub outcome_points: [i32; 3],
+}
+
+/// Rules kept with the day, by name.
+const BUILT_IN: [(&str, &str); 2] =
+    [("rps", include_str!("../rules/rps.txt")), ("rpsls", include_str!("../rules/rpsls.txt"))];
+
+/// The values of a key of a rules file, with the number of its line.
+type Values<'a> = (usize, Vec<&'a str>);
+
+fn points((line, values): Values, len: usize) -> Result<Vec<i32>, String> {
+    if values.len() != len {
+        return Err(format!("line {line}: expected {len} points, found {}", values.len()));
+    }
+    let point = |v: &&str| v.parse().map_err(|_| format!("line {line}: invalid points {v}"));
+    values.iter().map(point).collect()
+}
+
+fn letters((line, values): Values, len: usize) -> Result<Vec<char>, String> {
+    let mut letters = Vec::new();
+    for value in &values {
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_ascii_uppercase() && !letters.contains(&c) => letters.push(c),
+            _ => return Err(format!("line {line}: expected distinct letters from A to Z")),
+        }
+    }
+    if letters.len() != len {
+        return Err(format!("line {line}: expected {len} letters, found {}", letters.len()));
+    }
+    Ok(letters)
+}
+
+impl Rules {
+    /// Rock paper scissors, as in the puzzle.
+    pub fn puzzle() -> Rules { "rps".parse().unwrap() }
+
+    /// Parses a rules file: `key: values` lines, values separated by spaces, and `#` comments.
+    pub fn parse(name: &str, text: &str) -> Result<Rules, String> {
+        let mut values = HashMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once(':') else {
+                return Err(format!("line {}: expected `<key>: <values>`", i + 1));
+            };
+            if values.insert(key.trim(), (i + 1, value.split_whitespace().collect())).is_some() {
+                return Err(format!("line {}: `{}` again", i + 1, key.trim()));
+            }
+        }
+        let mut take = |key: &str| values.remove(key).ok_or_else(|| format!("missing `{key}`"));
+
+        let (_, shapes): Values = take("shapes")?;
+        let n = shapes.len();
+        if n < 3 || n % 2 == 0 {
+            return Err(format!("expected an odd number of shapes, at least 3, found {n}"));
+        }
+        let rules = Rules {
+            name: name.to_string(),
+            shapes: shapes.iter().map(|s| s.to_string()).collect(),
+            points: points(take("points")?, n)?,
+            opponent: letters(take("opponent")?, n)?,
+            player: letters(take("player")?, n)?,
+            outcomes: letters(take("outcomes")?, 3)?.try_into().unwrap(),
+            outcome_points: points(take("outcome points")?, 3)?.try_into().unwrap(),
+        };
+        if let Some((key, (line, _))) = values.into_iter().min_by_key(|(_, (line, _))| *line) {
+            return Err(format!("line {line}: unknown key `{key}`"));
+        }
+        Ok(rules)
+    }
+
+    fn shape(letters: &[char], letter: char) -> Option<Shape> {
+        letters.iter().position(|&l| l == letter).map(Shape)
+    }
+
+    /// How a round where we play `ours` against `theirs` ends for us.
+    pub fn outcome(&self, ours: Shape, theirs: Shape) -> Outcome {
+        let n = self.shapes.len();
+        match (ours.0 + n - theirs.0) % n {
+            0 => Outcome::Draw,
+            d if d <= (n - 1) / 2 => Outcome::Win,
+            _ => Outcome::Lose,
+        }
+    }
+
+    /// The shape to play against `theirs` for the round to end in `outcome`: the one right after
+    /// it in the cycle to win, and right before it to lose.
+    pub fn response(&self, theirs: Shape, outcome: Outcome) -> Shape {
+        let n = self.shapes.len();
+        match outcome {
+            Outcome::Lose => Shape((theirs.0 + n - 1) % n),
+            Outcome::Draw => theirs,
+            Outcome::Win => Shape((theirs.0 + 1) % n),
+        }
+    }
+
+    /// Our score for a round, with its second column read as `column`, or `None` if a letter
+    /// means nothing in these rules.
+    pub fn score(&self, (theirs, ours): Round, column: Column) -> Option<i32> {
+        let theirs = Rules::shape(&self.opponent, theirs)?;
+        let (ours, outcome) = match column {
+            Column::Shape => {
+                let ours = Rules::shape(&self.player, ours)?;
+                (ours, self.outcome(ours, theirs))
+            }
+            Column::Outcome => {
+                let outcome = Outcome::ALL[self.outcomes.iter().position(|&l| l == ours)?];
+                (self.response(theirs, outcome), outcome)
+            }
+        };
+        Some(self.points[ours.0] + self.outcome_points[outcome as usize])
+    }
+}
+
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{}", self.name) }
+}
+
+/// Reads the name of built-in rules (`rps` or `rpsls`), or else a path to a rules file.
+impl FromStr for Rules {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Rules, String> {
+        if let Some((name, text)) = BUILT_IN.iter().find(|(name, _)| *name == s) {
+            return Rules::parse(name, text);
+        }
+        let text = fs::read_to_string(s).map_err(|e| format!("Can't read rules {s}: {e}"))?;
+        Rules::parse(s, &text).map_err(|e| format!("{s}: {e}"))
+    }
+}
+
+fn parse_letter(line: Line<'_>, token: Option<&str>) -> Result<char, ParseError> {
+    let expected = "a letter from A to Z";
+    let token = line.require(token, expected)?;
+    let mut chars = token.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_uppercase() => Ok(c),
+        _ => Err(line.error(token, expected)),
+    }
+}
+
+fn parse_line(line: Line<'_>) -> Result<Round, ParseError> {
+    let mut parts = line.text.split_whitespace();
+    let round = (parse_letter(line, parts.next())?, parse_letter(line, parts.next())?);
+    match parts.next() {
+        Some(extra) => Err(line.error(extra, "end of line")),
+        None => Ok(round),
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Round>, ParseError> {
+    Line::all(DAY, input).map(parse_line).collect()
+}
+
+params! {
+    pub struct Params {
+        /// Rules of the game: `rps`, `rpsls` or the path to a rules file.
+        rules: Rules = Rules::puzzle(),
+    }
+}
+
+/// The violation of round `i` having letters the rules don't.
+fn unknown(rules: &Rules, i: usize, (theirs, ours): Round) -> Violation {
+    let found = format!("`{theirs} {ours}` in round {}", i + 1);
+    Violation::new(DAY, format!("letters of the rules {rules}"), found)
+}
+
+/// Every letter of the rounds means something in the rules, both ways of reading the second
+/// column.
+pub fn validate(rounds: &[Round], params: &Params) -> Result<(), Violation> {
+    for (i, &round) in rounds.iter().enumerate() {
+        for column in [Column::Shape, Column::Outcome] {
+            if params.rules.score(round, column).is_none() {
+                return Err(unknown(&params.rules, i, round));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Rounds with letters the rules don't have score nothing; `validate` reports them.
+fn total(rounds: &[Round], rules: &Rules, column: Column) -> i32 {
+    rounds.iter().filter_map(|&round| rules.score(round, column)).sum()
+}
+
+pub fn part1(rounds: &[Round], params: &Params) -> i32 {
+    total(rounds, &params.rules, Column::Shape)
+}
+
+pub fn part2(rounds: &[Round], params: &Params) -> i32 {
+    total(rounds, &params.rules, Column::Outcome)
+}
+
+/// The chance of each total score.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Distribution {
+    /// The lowest total, that of `chances[0]`.
+    pub min: i32,
+    pub chances: Vec<f64>,
+}
+
+impl Distribution {
+    fn totals(&self) -> impl Iterator<Item = (i32, f64)> + '_ {
+        self.chances.iter().enumerate().map(|(i, &chance)| (self.min + i as i32, chance))
+    }
+
+    pub fn mean(&self) -> f64 { self.totals().map(|(total, chance)| total as f64 * chance).sum() }
+
+    pub fn std_dev(&self) -> f64 {
+        let mean = self.mean();
+        let variance: f64 =
+            self.totals().map(|(total, chance)| (total as f64 - mean).powi(2) * chance).sum();
+        variance.sqrt()
+    }
+
+    /// The lowest total scored with a chance of at least `p` (from 0 to 1) of scoring it or less.
+    pub fn quantile(&self, p: f64) -> i32 {
+        let mut seen = 0.0;
+        for (total, chance) in self.totals() {
+            seen += chance;
+            if seen >= p {
+                return total;
+            }
+        }
+        self.min + self.chances.len() as i32 - 1
+    }
+}
+
+/// How the opponent's rounds could go for us, whatever the strategy guide says.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analysis {
+    /// The score of playing the best shape every round.
+    pub best: i32,
+    pub worst: i32,
+    /// The scores of playing a shape picked at random every round, each as likely.
+    pub random: Distribution,
+    /// Rounds the opponent plays each shape, in the order of `Rules::shapes`.
+    pub opponent: Vec<usize>,
+}
+
+/// Scores every shape we could play against each of the opponent's, or finds one of them missing
+/// from the rules.
+pub fn analyse(rounds: &[Round], rules: &Rules) -> Result<Analysis, Violation> {
+    let mut analysis = Analysis {
+        best: 0,
+        worst: 0,
+        random: Distribution { min: 0, chances: vec![1.0] },
+        opponent: vec![0; rules.shapes.len()],
+    };
+    let n = rules.shapes.len();
+    let chance = 1.0 / n as f64;
+    for (i, &round) in rounds.iter().enumerate() {
+        let theirs =
+            Rules::shape(&rules.opponent, round.0).ok_or_else(|| unknown(rules, i, round))?;
+        let scores: Vec<i32> = (0..n)
+            .map(|ours| {
+                rules.points[ours]
+                    + rules.outcome_points[rules.outcome(Shape(ours), theirs) as usize]
+            })
+            .collect();
+        let (best, worst) = (*scores.iter().max().unwrap(), *scores.iter().min().unwrap());
+        analysis.best += best;
+        analysis.worst += worst;
+
+        // Adds the score of this round, from `worst` to `best`, to every total so far.
+        let random = &analysis.random;
+        let mut chances = vec![0.0; random.chances.len() + (best - worst) as usize];
+        for (i, &before) in random.chances.iter().enumerate() {
+            for score in &scores {
+                chances[i + (score - worst) as usize] += before * chance;
+            }
+        }
+        analysis.random = Distribution { min: random.min + worst, chances };
+
+        analysis.opponent[theirs.0] += 1;
+    }
+    Ok(analysis)
+}
+
+pub fn solve(input: &str, params: &Params) -> Result<Answers<i32>, ParseError> {
+    Ok(Answers::solve(parse(input)?.as_slice(), |r| part1(r, params), |r| part2(r, params)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::params::Params as _;
+
+    #[test]
+    fn test_puzzle_rules() {
+        // The tables of the puzzle: scores by opponent (rows) and second column.
+        let part1 = [[4, 8, 3], [1, 5, 9], [7, 2, 6]];
+        let part2 = [[3, 4, 8], [1, 5, 9], [2, 6, 7]];
+        let rules = Rules::puzzle();
+        for (i, theirs) in ['A', 'B', 'C'].into_iter().enumerate() {
+            for (j, ours) in ['X', 'Y', 'Z'].into_iter().enumerate() {
+                assert_eq!(rules.score((theirs, ours), Column::Shape), Some(part1[i][j]));
+                assert_eq!(rules.score((theirs, ours), Column::Outcome), Some(part2[i][j]));
+            }
+        }
+        assert_eq!(rules.score(('A', 'W'), Column::Shape), None);
+    }
+
+    #[test]
+    fn test_rpsls() {
+        let rules: Rules = "rpsls".parse().unwrap();
+        let shape = |name| Shape(rules.shapes.iter().position(|s| s == name).unwrap());
+        for (winner, losers) in [
+            ("scissors", ["paper", "lizard"]),
+            ("paper", ["rock", "spock"]),
+            ("rock", ["lizard", "scissors"]),
+            ("lizard", ["spock", "paper"]),
+            ("spock", ["scissors", "rock"]),
+        ] {
+            for loser in losers {
+                assert_eq!(rules.outcome(shape(winner), shape(loser)), Outcome::Win);
+                assert_eq!(rules.outcome(shape(loser), shape(winner)), Outcome::Lose);
+            }
+        }
+        for theirs in 0..5 {
+            for outcome in Outcome::ALL {
+                let ours = rules.response(Shape(theirs), outcome);
+                assert_eq!(rules.outcome(ours, Shape(theirs)), outcome);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rules_errors() {
+        let rules = |text: &str| Rules::parse("test", text).unwrap_err();
+        let rps = include_str!("../rules/rps.txt");
+        let two = rps.replace("shapes: rock ", "shapes: ");
+        assert_eq!(rules(&two), "expected an odd number of shapes, at least 3, found 2");
+        let one = rps.replace("shapes: rock paper scissors", "shapes: rock");
+        assert_eq!(rules(&one), "expected an odd number of shapes, at least 3, found 1");
+        assert!(rules(&rps.replace("player: X Y Z", "player: X Y Y")).contains("distinct letters"));
+        assert!(rules(&rps.replace("points: 1 2 3", "points: 1 2")).contains("expected 3 points"));
+        assert_eq!(rules(&format!("{rps}colour: red\n")), "line 16: unknown key `colour`");
+        assert_eq!(rules("shapes: a b c\n"), "missing `points`");
+        assert!("no/such/rules.txt".parse::<Rules>().is_err());
+    }
+
+    #[test]
+    fn test_analyse() {
+        let rounds = parse("A Y\nB X\nC Z\nA X\n").unwrap();
+        let analysis = analyse(&rounds, &Rules::puzzle()).unwrap();
+        assert_eq!((analysis.best, analysis.worst), (32, 9));
+        assert_eq!(analysis.opponent, [2, 1, 1]);
+
+        // Against every way of playing the rounds, each as likely.
+        let mut counts = [0; 33];
+        for plays in 0..81 {
+            let total: i32 = (rounds.iter().enumerate())
+                .map(|(i, &(theirs, _))| {
+                    let ours = ['X', 'Y', 'Z'][plays / 3usize.pow(i as u32) % 3];
+                    Rules::puzzle().score((theirs, ours), Column::Shape).unwrap()
+                })
+                .sum();
+            counts[total as usize] += 1;
+        }
+        let random = &analysis.random;
+        assert_eq!(random.min, 9);
+        for (total, &count) in counts.iter().enumerate() {
+            let chance = random.chances.get(total.wrapping_sub(9)).copied().unwrap_or(0.0);
+            assert!((chance - count as f64 / 81.0).abs() < 1e-12, "{total}");
+        }
+        assert!((random.mean() - 20.0).abs() < 1e-9);
+        assert_eq!((random.quantile(0.0), random.quantile(1.0)), (9, 32));
+        let violation = analyse(&parse("A Y\nD X\n").unwrap(), &Rules::puzzle()).unwrap_err();
+        assert_eq!(violation.found, "`D X` in round 2");
+    }
+
+    #[test]
+    fn test_validate() {
+        let params = Params::puzzle();
+        assert!(validate(&parse("A Y\nC Z\n").unwrap(), &params).is_ok());
+        let violation = validate(&parse("A Y\nD Z\n").unwrap(), &params).unwrap_err();
+        let expected = "day2: assumes letters of the rules rps, found `D Z` in round 2";
+        assert_eq!(violation.to_string(), expected);
+    }
+
+    #[test]
+    fn test_unknown_letters() {
+        let params = Params::puzzle();
+        let rounds = parse("A Y\nB W\nD X\n").unwrap();
+        assert_eq!((part1(&rounds, &params), part2(&rounds, &params)), (8, 4));
+        assert!(validate(&rounds, &params).is_err());
+    }
+}
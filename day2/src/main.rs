@@ -1,50 +1,43 @@
-use std::io::{self, BufRead};
-
-fn parse_line(line: &str) -> (&str, &str) {
-    let mut parts = line.split_whitespace();
-    (parts.next().unwrap(), parts.next().unwrap())
-}
-
-fn calc_score_part1(choices: (&str, &str)) -> i32 {
-    match choices {
-        ("A", "X") => 1 + 3, // rock - rock = draw
-        ("A", "Y") => 2 + 6, // rock - paper = win
-        ("A", "Z") => 3 + 0, // rock - scissors = lose
-        ("B", "X") => 1 + 0, // paper - rock = lose
-        ("B", "Y") => 2 + 3, // paper - paper = draw
-        ("B", "Z") => 3 + 6, // paper - scissors = win
-        ("C", "X") => 1 + 6, // scissors - rock = win
-        ("C", "Y") => 2 + 0, // scissors - paper = lose
-        ("C", "Z") => 3 + 3, // scissors - scissors = draw
-        _ => panic!("Invalid input: {:?}", choices),
-    }
-}
-
-fn calc_score_part2(choices: (&str, &str)) -> i32 {
-    match choices {
-        ("A", "X") => 3 + 0, // rock - lose = scissors
-        ("A", "Y") => 1 + 3, // rock - draw = rock
-        ("A", "Z") => 2 + 6, // rock - win = paper
-        ("B", "X") => 1 + 0, // paper - lose = rock
-        ("B", "Y") => 2 + 3, // paper - draw = paper
-        ("B", "Z") => 3 + 6, // paper - win = scissors
-        ("C", "X") => 2 + 0, // scissors - lose = paper
-        ("C", "Y") => 3 + 3, // scissors - draw = scissors
-        ("C", "Z") => 1 + 6, // scissors - win = rock
-        _ => panic!("Invalid input: {:?}", choices),
-    }
-}
+use aoc_common::{input, params};
+use std::process::exit;
 
 fn main() {
-    let mut score1 = 0;
-    let mut score2 = 0;
+    // `--rules rpsls` or `--rules PATH` plays another game than rock paper scissors. Under the
+    // answers go the best and worst scores possible, what playing at random scores and how often
+    // the opponent plays each shape.
+    let args: params::Args<day2::Params> = params::args();
+    let input = input::read(&args.input).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        exit(1)
+    });
+    let rounds = day2::parse(&input).unwrap_or_else(|e| {
+        eprintln!("{}", e.report(&input));
+        exit(1)
+    });
+    if let Err(violation) = day2::validate(&rounds, &args.params) {
+        eprintln!("{violation}");
+        exit(1)
+    }
+    println!("Part 1: Score: {}", day2::part1(&rounds, &args.params));
+    println!("Part 2: Score: {}", day2::part2(&rounds, &args.params));
 
-    for line in io::stdin().lock().lines() {
-        let line = line.unwrap();
-        let choices = parse_line(&line);
-        score1 += calc_score_part1(choices);
-        score2 += calc_score_part2(choices);
+    let rules = &args.params.rules;
+    let analysis = day2::analyse(&rounds, rules).unwrap_or_else(|violation| {
+        eprintln!("{violation}");
+        exit(1)
+    });
+    println!("Best possible score: {}", analysis.best);
+    println!("Worst possible score: {}", analysis.worst);
+    let random = &analysis.random;
+    println!(
+        "Playing at random: {:.1} on average (standard deviation {:.1}), 90% between {} and {}",
+        random.mean(),
+        random.std_dev(),
+        random.quantile(0.05),
+        random.quantile(0.95)
+    );
+    for (shape, &count) in rules.shapes.iter().zip(&analysis.opponent) {
+        let percent = 100.0 * count as f64 / rounds.len().max(1) as f64;
+        println!("Opponent plays {shape}: {count} rounds ({percent:.1}%)");
     }
-    println!("Part 1: Score: {}", score1);
-    println!("Part 2: Score: {}", score2);
 }
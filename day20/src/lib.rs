@@ -0,0 +1,184 @@
+use aoc_common::parse::end_of_input;
+use aoc_common::{params, Answers, Line, ParseError};
+
+const DAY: &str = "day20";
+
+pub type Value = i64;
+/// The numbers with their original positions.
+pub type Msg = Vec<(usize, Value)>;
+
+fn mix(msg: &mut Msg) {
+    for i in 0..msg.len() {
+        let pos_before = msg.iter().position(|(x, _)| i == *x).unwrap();
+        mv(msg, pos_before, msg[pos_before].1);
+    }
+}
+
+fn score(msg: &Msg) -> Value {
+    let zero = msg.iter().position(|(_, x)| *x == 0).unwrap();
+    let n1k = msg[(zero + 1000) % msg.len()].1;
+    let n2k = msg[(zero + 2000) % msg.len()].1;
+    let n3k = msg[(zero + 3000) % msg.len()].1;
+    n1k + n2k + n3k
+}
+
+pub fn parse(input: &str) -> Result<Msg, ParseError> {
+    let msg: Msg = Line::all(DAY, input)
+        .enumerate()
+        .map(|(i, line)| Ok((i, line.parse_all("a number")?)))
+        .collect::<Result<_, _>>()?;
+    if msg.iter().filter(|(_, x)| *x == 0).count() != 1 {
+        return Err(end_of_input(DAY, input, "exactly one 0 in the message"));
+    }
+    Ok(msg)
+}
+
+params! {
+    pub struct Params {
+        /// What the numbers are multiplied by in part 2.
+        key: Value = 811589153,
+        /// Times the message is mixed in part 2.
+        mixes: usize = 10,
+    }
+}
+
+pub fn part1(msg: &Msg, _: &Params) -> Value {
+    let mut msg = msg.clone();
+    mix(&mut msg);
+    score(&msg)
+}
+
+pub fn part2(msg: &Msg, params: &Params) -> Value {
+    let mut msg: Msg = msg.iter().map(|&(i, n)| (i, n * params.key)).collect();
+    for _ in 0..params.mixes {
+        mix(&mut msg);
+    }
+    score(&msg)
+}
+
+pub fn solve(input: &str, params: &Params) -> Result<Answers<Value>, ParseError> {
+    Ok(Answers::solve(&parse(input)?, |m| part1(m, params), |m| part2(m, params)))
+}
+
+fn mv<T>(msg: &mut Vec<T>, from_index: usize, offset: Value) {
+    if msg.len() < 2 {
+        return;
+    }
+    let x = msg.remove(from_index);
+    let to_index = (from_index as Value + offset).rem_euclid(msg.len() as Value);
+    msg.insert(to_index as usize, x);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn mv_plus1() {
+        let mut msg = vec![0, 1, 2, 3, 4];
+        mv(&mut msg, 2, 1);
+        assert_eq!(msg, vec![0, 1, 3, 2, 4]);
+    }
+
+    #[test]
+    fn mv_min1() {
+        let mut msg = vec![0, 1, 2, 3, 4];
+        mv(&mut msg, 2, -1);
+        assert_eq!(msg, vec![0, 2, 1, 3, 4]);
+    }
+
+    #[test]
+    fn mv_plus2() {
+        let mut msg = vec![0, 1, 2, 3, 4];
+        mv(&mut msg, 2, 2);
+        assert_eq!(msg, vec![2, 0, 1, 3, 4]);
+    }
+
+    #[test]
+    fn mv_min2() {
+        let mut msg = vec![0, 1, 2, 3, 4];
+        mv(&mut msg, 2, -2);
+        assert_eq!(msg, vec![2, 0, 1, 3, 4]);
+    }
+
+    #[test]
+    fn mv_plus3() {
+        let mut msg = vec![0, 1, 2, 3, 4];
+        mv(&mut msg, 2, 3);
+        assert_eq!(msg, vec![0, 2, 1, 3, 4]);
+    }
+
+    #[test]
+    fn mv_cycle() {
+        let mut msg = vec![0, 1, 2, 3, 4];
+        mv(&mut msg, 2, 4);
+        assert_eq!(msg, vec![0, 1, 2, 3, 4]);
+        mv(&mut msg, 2, 5);
+        assert_eq!(msg, vec![0, 1, 3, 2, 4]);
+        mv(&mut msg, 3, 6);
+        assert_eq!(msg, vec![0, 2, 1, 3, 4]);
+        mv(&mut msg, 1, 7);
+        assert_eq!(msg, vec![2, 0, 1, 3, 4]);
+        mv(&mut msg, 4, 8);
+        assert_eq!(msg, vec![4, 2, 0, 1, 3]);
+    }
+
+    #[test]
+    fn mv_single() {
+        let mut msg = vec![0];
+        mv(&mut msg, 0, 3);
+        assert_eq!(msg, vec![0]);
+    }
+
+    fn msg_and_index() -> impl Strategy<Value = (Vec<usize>, usize)> {
+        (1..20usize).prop_flat_map(|len| (Just((0..len).collect()), 0..len))
+    }
+
+    proptest! {
+        #[test]
+        fn prop_mv_is_permutation((msg, from) in msg_and_index(), offset in -100..100 as Value) {
+            let mut moved = msg.clone();
+            mv(&mut moved, from, offset);
+            let mut sorted = moved.clone();
+            sorted.sort();
+            prop_assert_eq!(sorted, msg);
+        }
+
+        #[test]
+        fn prop_mv_keeps_others_in_order(
+            (msg, from) in msg_and_index(),
+            offset in -100..100 as Value,
+        ) {
+            let mut moved = msg.clone();
+            mv(&mut moved, from, offset);
+            let others: Vec<_> = msg.iter().filter(|&&x| x != msg[from]).collect();
+            let moved_others: Vec<_> = moved.iter().filter(|&&x| x != msg[from]).collect();
+            prop_assert_eq!(others, moved_others);
+        }
+
+        #[test]
+        fn prop_mv_lands_at_offset((msg, from) in msg_and_index(), offset in -100..100 as Value) {
+            prop_assume!(msg.len() > 1);
+            let mut moved = msg.clone();
+            mv(&mut moved, from, offset);
+            let to = (from as Value + offset).rem_euclid(msg.len() as Value - 1) as usize;
+            prop_assert_eq!(moved[to], msg[from]);
+        }
+
+        #[test]
+        fn prop_mv_is_undone_by_reverse(
+            (msg, from) in msg_and_index(),
+            offset in -100..100 as Value,
+        ) {
+            let mut moved = msg.clone();
+            mv(&mut moved, from, offset);
+            let to = moved.iter().position(|&x| x == msg[from]).unwrap();
+            mv(&mut moved, to, -offset);
+            // Positions are only meaningful up to rotation once an element wraps around.
+            let shift = moved.iter().position(|&x| x == msg[0]).unwrap();
+            moved.rotate_left(shift);
+            prop_assert_eq!(moved, msg);
+        }
+    }
+}
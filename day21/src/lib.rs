@@ -0,0 +1,335 @@
+use aoc_common::parse::end_of_input;
+use aoc_common::{Answers, Line, ParseError, Violation};
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+const DAY: &str = "day21";
+
+/// What each monkey yells, keyed by name.
+pub type Monkeys = HashMap<String, Expr>;
+/// For each monkey, the monkey whose expression uses it.
+pub type Deps = HashMap<String, String>;
+
+#[derive(Debug, Clone)]
+pub enum Expr {
+    Add(String, String),
+    Sub(String, String),
+    Mul(String, String),
+    Div(String, String),
+    Eq(String, String),
+    Val(i64),
+    X,
+}
+
+use Expr::*;
+
+impl Expr {
+    pub fn eval(&self, monkeys: &Monkeys) -> i64 {
+        match self {
+            Add(a, b) => monkeys[a].eval(monkeys) + monkeys[b].eval(monkeys),
+            Sub(a, b) => monkeys[a].eval(monkeys) - monkeys[b].eval(monkeys),
+            Mul(a, b) => monkeys[a].eval(monkeys) * monkeys[b].eval(monkeys),
+            Div(a, b) => monkeys[a].eval(monkeys) / monkeys[b].eval(monkeys),
+            Val(n) => *n,
+            X => panic!("And now what??"),
+            Eq(_, _) => panic!("And now what??"),
+        }
+    }
+
+    /// Renders the expression with every monkey substituted.
+    pub fn print(&self, monkeys: &Monkeys) -> String {
+        match self {
+            Add(a, b) => format!("({} + {})", monkeys[a].print(monkeys), monkeys[b].print(monkeys)),
+            Sub(a, b) => format!("({} - {})", monkeys[a].print(monkeys), monkeys[b].print(monkeys)),
+            Mul(a, b) => format!("({} * {})", monkeys[a].print(monkeys), monkeys[b].print(monkeys)),
+            Div(a, b) => format!("({} / {})", monkeys[a].print(monkeys), monkeys[b].print(monkeys)),
+            Eq(a, b) => format!("{} = {}", monkeys[a].print(monkeys), monkeys[b].print(monkeys)),
+            Val(x) => format!("{x}"),
+            X => String::from("X"),
+        }
+    }
+}
+
+/// Finds the value of `X` that makes the equation `expr` hold. Every branch not leading to `X`
+/// must already be reduced to a value.
+pub fn solve_for_x(expr: &str, monkeys: &Monkeys) -> i64 {
+    let mut val = 0;
+    let mut e = expr;
+
+    loop {
+        match &monkeys[e] {
+            Eq(a, b) => {
+                if let Val(x) = &monkeys[a] {
+                    val = *x;
+                    e = b;
+                } else if let Val(x) = &monkeys[b] {
+                    val = *x;
+                    e = a;
+                }
+            }
+            Add(a, b) => {
+                if let Val(x) = &monkeys[a] {
+                    val -= x;
+                    e = b;
+                } else if let Val(x) = &monkeys[b] {
+                    val -= x;
+                    e = a;
+                }
+            }
+            Div(a, b) => {
+                if let Val(x) = &monkeys[a] {
+                    val = x / val;
+                    e = b;
+                } else if let Val(x) = &monkeys[b] {
+                    val *= x;
+                    e = a;
+                }
+            }
+            Mul(a, b) => {
+                if let Val(x) = &monkeys[a] {
+                    val /= x;
+                    e = b;
+                } else if let Val(x) = &monkeys[b] {
+                    val /= x;
+                    e = a;
+                }
+            }
+            Sub(a, b) => {
+                if let Val(x) = &monkeys[a] {
+                    val = x - val;
+                    e = b;
+                } else if let Val(x) = monkeys[b] {
+                    val += x;
+                    e = a;
+                }
+            }
+            X => {
+                return val;
+            }
+            Val(_) => panic!("Now what?"),
+        }
+    }
+}
+
+/// Parses the monkeys, also returning which monkey depends on each monkey.
+pub fn parse(input: &str) -> Result<(Monkeys, Deps), ParseError> {
+    let line_re =
+        Regex::new(r"^(?P<name>.{4}): ((?P<val>\d+)|(?P<a>.{4}) (?P<op>[-+*/]) (?P<b>.{4}))$")
+            .unwrap();
+
+    let mut deps = HashMap::new();
+
+    let mut monkeys = HashMap::new();
+    for line in Line::all(DAY, input) {
+        let expected = "`<name>: <number>` or `<name>: <name> <op> <name>`";
+        let cap = line_re.captures(line.text).ok_or_else(|| line.error(line.text, expected))?;
+        let name = cap.name("name").unwrap().as_str();
+        monkeys.insert(
+            String::from(name),
+            match cap.name("val") {
+                Some(x) => Val(line.parse(x.as_str(), "a number")?),
+                None => {
+                    let a = String::from(cap.name("a").unwrap().as_str());
+                    let b = String::from(cap.name("b").unwrap().as_str());
+                    deps.insert(a.clone(), String::from(name));
+                    deps.insert(b.clone(), String::from(name));
+                    match cap.name("op").unwrap().as_str() {
+                        "+" => Add(a, b),
+                        "-" => Sub(a, b),
+                        "*" => Mul(a, b),
+                        "/" => Div(a, b),
+                        _ => panic!("Can't happen :-)"),
+                    }
+                }
+            },
+        );
+    }
+    let used = deps.keys().map(|name| name.as_str());
+    for name in ["root", "humn"].into_iter().chain(used) {
+        if !monkeys.contains_key(name) {
+            return Err(end_of_input(DAY, input, format!("a monkey named `{name}`")));
+        }
+    }
+    Ok((monkeys, deps))
+}
+
+/// The monkeys form a tree under `root`, which adds two monkeys, with `humn` somewhere in it.
+pub fn validate((monkeys, deps): &(Monkeys, Deps)) -> Result<(), Violation> {
+    let mut uses: HashMap<&str, usize> = HashMap::new();
+    for expr in monkeys.values() {
+        if let Add(a, b) | Sub(a, b) | Mul(a, b) | Div(a, b) = expr {
+            *uses.entry(a).or_default() += 1;
+            *uses.entry(b).or_default() += 1;
+        }
+    }
+    if let Some((name, count)) = uses.iter().filter(|(_, count)| **count > 1).min() {
+        let found = format!("`{name}` used {count} times");
+        return Err(Violation::new(DAY, "every monkey used by at most one other", found));
+    }
+    if let Some(user) = deps.get("root") {
+        let found = format!("`{user}` using it");
+        return Err(Violation::new(DAY, "`root` used by no other monkey", found));
+    }
+    let found = match &monkeys["root"] {
+        Add(_, _) => None,
+        Sub(a, b) => Some(format!("`{a} - {b}`")),
+        Mul(a, b) => Some(format!("`{a} * {b}`")),
+        Div(a, b) => Some(format!("`{a} / {b}`")),
+        Val(n) => Some(format!("`{n}`")),
+        Eq(_, _) | X => unreachable!("Only part 2 makes equations and unknowns"),
+    };
+    if let Some(found) = found {
+        return Err(Violation::new(DAY, "`root` adding two monkeys", found));
+    }
+    // Monkeys used once each can still go round in a loop, which never gets to `root`.
+    let mut monkey = "humn";
+    for _ in 0..monkeys.len() {
+        let Some(user) = deps.get(monkey) else { break };
+        monkey = user;
+    }
+    if monkey != "root" {
+        let found = format!("`humn` ending up with `{monkey}`");
+        return Err(Violation::new(DAY, "`root` depending on `humn`", found));
+    }
+    Ok(())
+}
+
+pub fn part1((monkeys, _): &(Monkeys, Deps)) -> i64 { monkeys["root"].eval(monkeys) }
+
+pub fn part2((monkeys, deps): &(Monkeys, Deps)) -> i64 {
+    let mut monkeys = monkeys.clone();
+    let Add(a, b) = &monkeys["root"] else { panic!("Root should add two monkeys") };
+    monkeys.insert(String::from("root"), Eq(a.clone(), b.clone()));
+    monkeys.insert(String::from("humn"), X);
+
+    let mut to_resolve = "humn";
+    while deps.contains_key(to_resolve) {
+        let looking = to_resolve;
+        to_resolve = &deps[to_resolve];
+        match &monkeys[to_resolve] {
+            Add(a, b) | Sub(a, b) | Mul(a, b) | Div(a, b) | Eq(a, b) if a == looking => {
+                monkeys.insert(b.clone(), Val(monkeys[b].eval(&monkeys)));
+            }
+            Add(a, b) | Sub(a, b) | Mul(a, b) | Div(a, b) | Eq(a, b) if b == looking => {
+                monkeys.insert(a.clone(), Val(monkeys[a].eval(&monkeys)));
+            }
+            Val(_) => (),
+            X => (),
+            x => panic!("??? {:?}", x),
+        }
+    }
+    solve_for_x("root", &monkeys)
+}
+
+pub fn solve(input: &str) -> Result<Answers<i64>, ParseError> {
+    Ok(Answers::solve(&parse(input)?, part1, part2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_eq() {
+        let mut monkeys: Monkeys = HashMap::new();
+        // 2 + x = 5
+        monkeys.insert(String::from("root"), Eq(String::from("a"), String::from("b")));
+        monkeys.insert(String::from("humn"), X);
+
+        monkeys.insert(String::from("a"), Add(String::from("c"), String::from("humn")));
+        monkeys.insert(String::from("b"), Val(5));
+        monkeys.insert(String::from("c"), Val(2));
+
+        assert_eq!(solve_for_x("root", &monkeys), 3);
+
+        // 5 = 2 + x
+        monkeys.insert(String::from("root"), Eq(String::from("b"), String::from("a")));
+
+        assert_eq!(solve_for_x("root", &monkeys), 3);
+    }
+
+    #[test]
+    fn test_solve_add() {
+        let mut monkeys: Monkeys = HashMap::new();
+        // 2 + x = 5
+        monkeys.insert(String::from("root"), Eq(String::from("a"), String::from("b")));
+        monkeys.insert(String::from("humn"), X);
+
+        monkeys.insert(String::from("a"), Add(String::from("c"), String::from("humn")));
+        monkeys.insert(String::from("b"), Val(5));
+        monkeys.insert(String::from("c"), Val(2));
+
+        assert_eq!(solve_for_x("root", &monkeys), 3);
+
+        // x + 2 = 5
+        monkeys.insert(String::from("a"), Add(String::from("humn"), String::from("c")));
+
+        assert_eq!(solve_for_x("root", &monkeys), 3);
+    }
+
+    #[test]
+    fn test_solve_sub() {
+        let mut monkeys: Monkeys = HashMap::new();
+        // 2 - x = 5
+        monkeys.insert(String::from("root"), Eq(String::from("a"), String::from("b")));
+        monkeys.insert(String::from("humn"), X);
+
+        monkeys.insert(String::from("a"), Sub(String::from("c"), String::from("humn")));
+        monkeys.insert(String::from("b"), Val(5));
+        monkeys.insert(String::from("c"), Val(2));
+
+        assert_eq!(solve_for_x("root", &monkeys), -3);
+
+        // x - 2 = 5
+        monkeys.insert(String::from("a"), Sub(String::from("humn"), String::from("c")));
+
+        assert_eq!(solve_for_x("root", &monkeys), 7);
+    }
+
+    #[test]
+    fn test_solve_mul() {
+        let mut monkeys: Monkeys = HashMap::new();
+        // 2 * x = 6
+        monkeys.insert(String::from("root"), Eq(String::from("a"), String::from("b")));
+        monkeys.insert(String::from("humn"), X);
+
+        monkeys.insert(String::from("a"), Mul(String::from("c"), String::from("humn")));
+        monkeys.insert(String::from("b"), Val(6));
+        monkeys.insert(String::from("c"), Val(2));
+
+        assert_eq!(solve_for_x("root", &monkeys), 3);
+
+        // x * 2 = 6
+        monkeys.insert(String::from("a"), Mul(String::from("humn"), String::from("c")));
+
+        assert_eq!(solve_for_x("root", &monkeys), 3);
+    }
+
+    #[test]
+    fn test_solve_div() {
+        let mut monkeys: Monkeys = HashMap::new();
+        // 6 / x = 2
+        monkeys.insert(String::from("root"), Eq(String::from("a"), String::from("b")));
+        monkeys.insert(String::from("humn"), X);
+
+        monkeys.insert(String::from("a"), Div(String::from("c"), String::from("humn")));
+        monkeys.insert(String::from("b"), Val(2));
+        monkeys.insert(String::from("c"), Val(6));
+
+        assert_eq!(solve_for_x("root", &monkeys), 3);
+
+        // x / 6 = 2
+        monkeys.insert(String::from("a"), Div(String::from("humn"), String::from("c")));
+
+        assert_eq!(solve_for_x("root", &monkeys), 12);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse("root: pppw + sjmn\npppw: 2\nsjmn: 3x\n").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+        let err = parse("root: pppw + sjmn\npppw: 2\nsjmn: 3\n").unwrap_err();
+        assert_eq!(err.expected, "a monkey named `humn`");
+    }
+}
@@ -0,0 +1,563 @@
+use aoc_common::parse::end_of_input;
+use aoc_common::picture::Picture;
+use aoc_common::{Answers, BBox2, Dir4, Line, ParseError, Point2, Violation};
+use core::ops::Range;
+use std::collections::{HashMap, HashSet};
+
+const DAY: &str = "day22";
+
+/// Map position, `x` is the column and `y` the row.
+type Pos = Point2<i32>;
+
+/// The board, along with where we are on it and how its faces fold into a cube.
+#[derive(Clone)]
+pub struct World {
+    data: Vec<Vec<char>>,
+    col_ranges: Vec<Range<usize>>,
+    row_ranges: Vec<Range<usize>>,
+    pos: Pos,
+    dir: Dir4,
+    /// Everywhere `pos` has been.
+    trail: HashSet<Pos>,
+    edge_size: i32,
+    connections: BiMap<Edge>,
+}
+
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
+struct Edge {
+    side: Dir4,
+    quadrant: Pos,
+}
+
+#[derive(Clone)]
+struct BiMap<T>
+where
+    T: Eq + std::hash::Hash + Clone,
+{
+    map: HashMap<T, T>,
+}
+
+impl<T> BiMap<T>
+where
+    T: Eq + std::hash::Hash + Clone,
+{
+    fn new() -> BiMap<T> { BiMap { map: HashMap::new() } }
+    fn insert(&mut self, k: T, v: T) {
+        assert!(self.map.insert(k.clone(), v.clone()).is_none());
+        assert!(self.map.insert(v, k).is_none());
+    }
+    fn get(&self, k: &T) -> Option<&T> { self.map.get(k) }
+}
+
+fn clamp(mut n: i32, range: &Range<usize>) -> i32 {
+    while n < range.start as i32 {
+        n += (range.end - range.start) as i32;
+    }
+    while n >= range.end as i32 {
+        n -= (range.end - range.start) as i32;
+    }
+    n
+}
+
+impl World {
+    fn new(world: Vec<Vec<char>>) -> World {
+        let mut col_ranges = Vec::new();
+        let mut longest_len = 0;
+
+        for line in &world {
+            let start = line.iter().position(|c| *c != EMPTY).unwrap();
+            let end = line[start..].iter().position(|c| *c == EMPTY).unwrap_or(line.len());
+            col_ranges.push(start..end);
+            longest_len = longest_len.max(line.len());
+        }
+
+        let mut row_ranges = Vec::new();
+        for col in 0..longest_len {
+            let start =
+                world.iter().position(|row| row.get(col).unwrap_or(&EMPTY) != &EMPTY).unwrap();
+            let end = start
+                + world[start..]
+                    .iter()
+                    .position(|row| row.get(col).unwrap_or(&EMPTY) == &EMPTY)
+                    .unwrap_or(world.len() - start);
+            row_ranges.push(start..end);
+        }
+
+        let initial_col = col_ranges[0].start as i32;
+
+        // 4x3 or 3x4 quadrants.
+        let edge_size = row_ranges.len().max(col_ranges.len()) as i32 / 4;
+
+        // Ugh...
+        let mut connections = BiMap::new();
+        if edge_size == 4 {
+            connections.insert(
+                Edge { quadrant: Pos { x: 2, y: 0 }, side: Dir4::W },
+                Edge { quadrant: Pos { x: 1, y: 1 }, side: Dir4::N },
+            );
+            connections.insert(
+                Edge { quadrant: Pos { x: 2, y: 0 }, side: Dir4::N },
+                Edge { quadrant: Pos { x: 0, y: 1 }, side: Dir4::N },
+            );
+            connections.insert(
+                Edge { quadrant: Pos { x: 2, y: 0 }, side: Dir4::E },
+                Edge { quadrant: Pos { x: 3, y: 2 }, side: Dir4::E },
+            );
+            connections.insert(
+                Edge { quadrant: Pos { x: 0, y: 1 }, side: Dir4::W },
+                Edge { quadrant: Pos { x: 3, y: 2 }, side: Dir4::S },
+            );
+            connections.insert(
+                Edge { quadrant: Pos { x: 0, y: 1 }, side: Dir4::S },
+                Edge { quadrant: Pos { x: 2, y: 2 }, side: Dir4::S },
+            );
+            connections.insert(
+                Edge { quadrant: Pos { x: 1, y: 1 }, side: Dir4::S },
+                Edge { quadrant: Pos { x: 2, y: 2 }, side: Dir4::W },
+            );
+            connections.insert(
+                Edge { quadrant: Pos { x: 2, y: 1 }, side: Dir4::E },
+                Edge { quadrant: Pos { x: 3, y: 2 }, side: Dir4::N },
+            );
+        } else if edge_size == 50 {
+            connections.insert(
+                Edge { quadrant: Pos { x: 1, y: 0 }, side: Dir4::W },
+                Edge { quadrant: Pos { x: 0, y: 2 }, side: Dir4::W },
+            );
+            connections.insert(
+                Edge { quadrant: Pos { x: 1, y: 0 }, side: Dir4::N },
+                Edge { quadrant: Pos { x: 0, y: 3 }, side: Dir4::W },
+            );
+            connections.insert(
+                Edge { quadrant: Pos { x: 2, y: 0 }, side: Dir4::N },
+                Edge { quadrant: Pos { x: 0, y: 3 }, side: Dir4::S },
+            );
+            connections.insert(
+                Edge { quadrant: Pos { x: 2, y: 0 }, side: Dir4::E },
+                Edge { quadrant: Pos { x: 1, y: 2 }, side: Dir4::E },
+            );
+            connections.insert(
+                Edge { quadrant: Pos { x: 2, y: 0 }, side: Dir4::S },
+                Edge { quadrant: Pos { x: 1, y: 1 }, side: Dir4::E },
+            );
+            connections.insert(
+                Edge { quadrant: Pos { x: 1, y: 1 }, side: Dir4::W },
+                Edge { quadrant: Pos { x: 0, y: 2 }, side: Dir4::N },
+            );
+            connections.insert(
+                Edge { quadrant: Pos { x: 0, y: 3 }, side: Dir4::E },
+                Edge { quadrant: Pos { x: 1, y: 2 }, side: Dir4::S },
+            );
+        }
+
+        World {
+            data: world,
+            col_ranges,
+            row_ranges,
+            edge_size,
+            pos: Pos { x: initial_col, y: 0 },
+            dir: Dir4::E,
+            trail: HashSet::from([Pos { x: initial_col, y: 0 }]),
+            connections,
+        }
+    }
+
+    fn mv(&mut self, dist: i32) {
+        for _ in 0..dist {
+            let mut next_pos = self.pos + self.dir.delta();
+            if matches!(self.dir, Dir4::N | Dir4::S) {
+                next_pos.y = clamp(next_pos.y, &self.row_ranges[next_pos.x as usize]);
+            } else {
+                next_pos.x = clamp(next_pos.x, &self.col_ranges[next_pos.y as usize]);
+            }
+            if self.get(&next_pos) != OPEN {
+                break;
+            }
+            self.pos = next_pos;
+            self.trail.insert(next_pos);
+        }
+    }
+
+    fn mv2(&mut self, dist: i32) {
+        for _ in 0..dist {
+            let mut next_pos = self.pos + self.dir.delta();
+            let quadrant = Pos { x: self.pos.x / self.edge_size, y: self.pos.y / self.edge_size };
+            let index_on_side;
+            let side = if matches!(self.dir, Dir4::N | Dir4::S) {
+                index_on_side = next_pos.x % self.edge_size;
+                let range = &self.row_ranges[next_pos.x as usize];
+                if next_pos.y < range.start as i32 {
+                    Some(Dir4::N)
+                } else if next_pos.y >= range.end as i32 {
+                    Some(Dir4::S)
+                } else {
+                    None
+                }
+            } else {
+                index_on_side = next_pos.y % self.edge_size;
+                let range = &self.col_ranges[next_pos.y as usize];
+                if next_pos.x < range.start as i32 {
+                    Some(Dir4::W)
+                } else if next_pos.x >= range.end as i32 {
+                    Some(Dir4::E)
+                } else {
+                    None
+                }
+            };
+
+            if let Some(side) = side {
+                let connection = self.connections.get(&Edge { quadrant, side }).unwrap();
+                next_pos = Pos {
+                    x: connection.quadrant.x * self.edge_size,
+                    y: connection.quadrant.y * self.edge_size,
+                };
+                match &connection.side {
+                    Dir4::S => {
+                        next_pos.y += self.edge_size - 1;
+                    }
+                    Dir4::E => {
+                        next_pos.x += self.edge_size - 1;
+                    }
+                    _ => {}
+                }
+                match (&side, &connection.side) {
+                    (Dir4::N, Dir4::W)
+                    | (Dir4::W, Dir4::E)
+                    | (Dir4::E, Dir4::W)
+                    | (Dir4::S, Dir4::E) => next_pos.y += index_on_side,
+                    (Dir4::N, Dir4::S)
+                    | (Dir4::W, Dir4::N)
+                    | (Dir4::E, Dir4::S)
+                    | (Dir4::S, Dir4::N) => next_pos.x += index_on_side,
+                    (Dir4::N, Dir4::E)
+                    | (Dir4::W, Dir4::W)
+                    | (Dir4::E, Dir4::E)
+                    | (Dir4::S, Dir4::W) => next_pos.y += self.edge_size - 1 - index_on_side,
+                    (Dir4::N, Dir4::N)
+                    | (Dir4::W, Dir4::S)
+                    | (Dir4::E, Dir4::N)
+                    | (Dir4::S, Dir4::S) => next_pos.x += self.edge_size - 1 - index_on_side,
+                }
+
+                if self.get(&next_pos) != OPEN {
+                    break;
+                }
+                match (&side, &connection.side) {
+                    (Dir4::N, Dir4::E)
+                    | (Dir4::W, Dir4::N)
+                    | (Dir4::E, Dir4::S)
+                    | (Dir4::S, Dir4::W) => self.turn('L'),
+                    (Dir4::N, Dir4::W)
+                    | (Dir4::W, Dir4::S)
+                    | (Dir4::E, Dir4::N)
+                    | (Dir4::S, Dir4::E) => self.turn('R'),
+                    (Dir4::N, Dir4::S)
+                    | (Dir4::W, Dir4::E)
+                    | (Dir4::E, Dir4::W)
+                    | (Dir4::S, Dir4::N) => {}
+                    (Dir4::N, Dir4::N)
+                    | (Dir4::W, Dir4::W)
+                    | (Dir4::E, Dir4::E)
+                    | (Dir4::S, Dir4::S) => {
+                        self.turn('R');
+                        self.turn('R');
+                    }
+                }
+            } else {
+                if self.get(&next_pos) != OPEN {
+                    break;
+                }
+            }
+            self.pos = next_pos;
+            self.trail.insert(next_pos);
+        }
+    }
+
+    fn turn(&mut self, dir: char) {
+        self.dir = match dir {
+            'L' => self.dir.turn_left(),
+            'R' => self.dir.turn_right(),
+            _ => panic!("Invalid direction {}", dir),
+        }
+    }
+
+    fn get(&self, p: &Pos) -> char { self.data[p.y as usize][p.x as usize] }
+
+    fn facing(&self) -> i32 {
+        match self.dir {
+            Dir4::E => 0,
+            Dir4::S => 1,
+            Dir4::W => 2,
+            Dir4::N => 3,
+        }
+    }
+
+    fn run(&mut self, course: &str, cube: bool) -> i32 {
+        let mut it = course.chars();
+        let mut c = it.next();
+        let mut dist = 0;
+        loop {
+            match c {
+                Some(d) if !d.is_ascii_digit() => {
+                    if cube {
+                        self.mv2(dist)
+                    } else {
+                        self.mv(dist);
+                    }
+                    self.turn(d);
+                    dist = 0;
+                }
+                Some(i) => {
+                    dist *= 10;
+                    dist += i.to_digit(10).unwrap() as i32;
+                }
+                None => {
+                    if cube {
+                        self.mv2(dist)
+                    } else {
+                        self.mv(dist);
+                    }
+                    break;
+                }
+            }
+            c = it.next();
+        }
+
+        1000 * (self.pos.y + 1) + 4 * (self.pos.x + 1) + self.facing()
+    }
+}
+
+static EMPTY: char = ' ';
+static OPEN: char = '.';
+
+/// Checks that every row and column of the board is a single run of tiles, which `World::new`
+/// relies on.
+fn check_board(rows: &[Line]) -> Result<(), ParseError> {
+    for row in rows {
+        let start = row.text.len() - row.text.trim_start().len();
+        let end = row.text.trim_end().len();
+        if let Some(gap) = row.text[start..end].find(EMPTY) {
+            return Err(row.error_at_char(start + gap, "`.` or `#`, without gaps in a row"));
+        }
+    }
+    let width = rows.iter().map(|row| row.text.len()).max().unwrap_or(0);
+    for col in 0..width {
+        let tile = |row: &Line| row.text.as_bytes().get(col).is_some_and(|&c| c != b' ');
+        let Some(first) = rows.iter().position(tile) else {
+            let row = rows.iter().find(|row| row.text.len() > col).unwrap();
+            return Err(row.error_at_char(col, "a tile somewhere in this column"));
+        };
+        let last = rows.iter().rposition(tile).unwrap();
+        if let Some(gap) = rows[first..last].iter().find(|row| !tile(row)) {
+            return Err(gap.error_at_char(col, "`.` or `#`, without gaps in a column"));
+        }
+    }
+    Ok(())
+}
+
+/// The board and the path to follow.
+pub fn parse(input: &str) -> Result<(World, &str), ParseError> {
+    let mut lines = Line::all(DAY, input);
+    let mut rows = Vec::new();
+    for line in lines.by_ref().take_while(|line| !line.text.is_empty()) {
+        if let Some(i) = line.text.chars().position(|c| !" .#".contains(c)) {
+            return Err(line.error_at_char(i, "` `, `.` or `#`"));
+        }
+        if line.text.trim().is_empty() {
+            return Err(line.error("", "a row of the board"));
+        }
+        rows.push(line);
+    }
+    if rows.is_empty() {
+        return Err(end_of_input(DAY, input, "the board"));
+    }
+    let course = lines.next().ok_or_else(|| end_of_input(DAY, input, "the path"))?;
+    if let Some(i) = course.text.chars().position(|c| !c.is_ascii_digit() && c != 'L' && c != 'R') {
+        return Err(course.error_at_char(i, "a number, `L` or `R`"));
+    }
+    check_board(&rows)?;
+    let world = rows.iter().map(|row| row.text.chars().collect()).collect();
+    Ok((World::new(world), course.text))
+}
+
+/// The nets of a cube that `World::new` knows how to fold, by edge size: the quadrants of the
+/// board that are faces, as columns and rows.
+const NETS: [(i32, [(usize, usize); 6]); 2] = [
+    (4, [(2, 0), (0, 1), (1, 1), (2, 1), (2, 2), (3, 2)]),
+    (50, [(1, 0), (2, 0), (1, 1), (0, 2), (1, 2), (0, 3)]),
+];
+
+/// The board is one of the two nets the cube is folded from, and the path starts on an open tile.
+pub fn validate((world, _): &(World, &str)) -> Result<(), Violation> {
+    let (width, height) = (world.row_ranges.len(), world.col_ranges.len());
+    let net = NETS.iter().find(|(size, _)| *size == world.edge_size);
+    let Some((size, faces)) = net else {
+        let found = format!("a board of {width} by {height} tiles");
+        return Err(Violation::new(DAY, "faces of 4 or 50 tiles across", found));
+    };
+    let size = *size as usize;
+    let columns = faces.iter().map(|(x, _)| x + 1).max().unwrap();
+    let rows = faces.iter().map(|(_, y)| y + 1).max().unwrap();
+    let expected = format!("the net of the {size} tile cube, {columns} by {rows} faces");
+    if (width, height) != (columns * size, rows * size) {
+        return Err(Violation::new(DAY, expected, format!("{width} by {height} tiles")));
+    }
+    for (y, range) in world.col_ranges.iter().enumerate() {
+        let row = faces.iter().filter(|(_, fy)| *fy == y / size).map(|(fx, _)| *fx);
+        let start = row.clone().min().unwrap() * size;
+        let end = (row.max().unwrap() + 1) * size;
+        if *range != (start..end) {
+            let found = format!("tiles {} to {} in row {}", range.start + 1, range.end, y + 1);
+            return Err(Violation::new(DAY, expected, found));
+        }
+    }
+    if world.get(&world.pos) != OPEN {
+        let found = format!("`{}`", world.get(&world.pos));
+        return Err(Violation::new(DAY, "an open first tile on the top row", found));
+    }
+    Ok(())
+}
+
+pub fn part1((world, course): &(World, &str)) -> i32 { world.clone().run(course, false) }
+
+pub fn part2((world, course): &(World, &str)) -> i32 { world.clone().run(course, true) }
+
+/// The board, with the path walked in part 1 in orange and where it ends in red.
+pub fn picture((world, course): &(World, &str)) -> Picture {
+    let mut world = world.clone();
+    world.run(course, false);
+    let bounds = BBox2 {
+        min: Pos::origin(),
+        max: Pos { x: world.row_ranges.len() as i32 - 1, y: world.col_ranges.len() as i32 - 1 },
+    };
+    let palette = vec![[0, 0, 0], [200, 200, 190], [70, 70, 80], [240, 150, 40], [220, 30, 30]];
+    Picture::draw(&bounds, palette, |p| {
+        let c = world.data[p.y as usize].get(p.x as usize).unwrap_or(&EMPTY);
+        if p == world.pos {
+            4
+        } else if world.trail.contains(&p) {
+            3
+        } else if *c == OPEN {
+            1
+        } else if *c == EMPTY {
+            0
+        } else {
+            2
+        }
+    })
+}
+
+pub fn solve(input: &str) -> Result<Answers<i32>, ParseError> {
+    Ok(Answers::solve(&parse(input)?, part1, part2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turn() {
+        let mut world = small_world();
+        world.dir = Dir4::S;
+        world.turn('L');
+        assert_eq!(world.dir, Dir4::E);
+        world.dir = Dir4::S;
+        world.turn('R');
+        assert_eq!(world.dir, Dir4::W);
+
+        world.dir = Dir4::E;
+        world.turn('L');
+        assert_eq!(world.dir, Dir4::N);
+        world.dir = Dir4::E;
+        world.turn('R');
+        assert_eq!(world.dir, Dir4::S);
+    }
+
+    #[test]
+    fn test_clamp() {
+        assert_eq!(clamp(10, &(5..12)), 10);
+        assert_eq!(clamp(10, &(5..10)), 5);
+        assert_eq!(clamp(10, &(5..9)), 6);
+        assert_eq!(clamp(10, &(10..20)), 10);
+        assert_eq!(clamp(9, &(10..20)), 19);
+        assert_eq!(clamp(8, &(10..20)), 18);
+    }
+
+    #[test]
+    fn test_mv2_w2n() {
+        let mut world = small_world();
+        world.pos = Pos { x: 8, y: 2 };
+        world.dir = Dir4::W;
+
+        world.mv2(1);
+        assert_eq!(world.pos, Pos { x: 6, y: 4 });
+        assert_eq!(world.dir, Dir4::S);
+    }
+
+    #[test]
+    fn test_mv2_n2n() {
+        let mut world = small_world();
+        world.pos = Pos { x: 1, y: 4 };
+        world.dir = Dir4::N;
+
+        world.mv2(1);
+        assert_eq!(world.pos, Pos { x: 10, y: 0 });
+        assert_eq!(world.dir, Dir4::S);
+    }
+
+    #[test]
+    fn test_mv2_n2w() {
+        let mut world = small_world();
+        world.pos = Pos { x: 6, y: 4 };
+        world.dir = Dir4::N;
+
+        world.mv2(1);
+        assert_eq!(world.pos, Pos { x: 8, y: 2 });
+        assert_eq!(world.dir, Dir4::E);
+    }
+
+    #[test]
+    fn test_mv2_e2e() {
+        let mut world = small_world();
+        world.pos = Pos { x: 11, y: 2 };
+        world.dir = Dir4::E;
+
+        world.mv2(1);
+        assert_eq!(world.pos, Pos { x: 15, y: 9 });
+        assert_eq!(world.dir, Dir4::W);
+    }
+
+    fn small_world() -> World {
+        World::new(vec![
+            "        ....".chars().collect(),
+            "        ....".chars().collect(),
+            "        ....".chars().collect(),
+            "        ....".chars().collect(),
+            "............".chars().collect(),
+            "............".chars().collect(),
+            "............".chars().collect(),
+            "............".chars().collect(),
+            "        ........".chars().collect(),
+            "        ........".chars().collect(),
+            "        ........".chars().collect(),
+            "        ........".chars().collect(),
+        ])
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse("  ..\n  .#\n\n10R5X\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.found.as_deref()), (4, 5, Some("X")));
+        let err = parse("  ..\n  .#\n").err().unwrap();
+        assert_eq!((err.line, err.expected.as_str()), (3, "the path"));
+        let err = parse("  ..\n  .#\n\n10R5\n").err().unwrap();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (1, 1, "a tile somewhere in this column")
+        );
+        let err = parse("..\n. \n..\n\n10R5\n").err().unwrap();
+        assert_eq!((err.line, err.column), (2, 2));
+        let err = parse(".. .\n\n10R5\n").err().unwrap();
+        assert_eq!((err.line, err.column), (1, 3));
+    }
+}
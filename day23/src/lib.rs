@@ -0,0 +1,152 @@
+use aoc_common::animate::{draw, Animation};
+use aoc_common::picture::Picture;
+use aoc_common::{params, Answers, BBox2, Dir8, Line, ParseError, Point2, Violation};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+const DAY: &str = "day23";
+
+pub type Pos = Point2<i32>;
+
+const DIRECTIONS: [[Dir8; 3]; 4] = [
+    [Dir8::N, Dir8::NE, Dir8::NW],
+    [Dir8::S, Dir8::SE, Dir8::SW],
+    [Dir8::W, Dir8::NW, Dir8::SW],
+    [Dir8::E, Dir8::NE, Dir8::SE],
+];
+
+/// Positions of the elves.
+pub fn parse(input: &str) -> Result<HashSet<Pos>, ParseError> {
+    let mut world = HashSet::new();
+    for (y, line) in Line::all(DAY, input).enumerate() {
+        for (x, c) in line.text.chars().enumerate() {
+            match c {
+                '#' => {
+                    world.insert(Pos { x: x as i32, y: y as i32 });
+                }
+                '.' => {}
+                _ => return Err(line.error_at_char(x, "`#` or `.`")),
+            }
+        }
+    }
+    Ok(world)
+}
+
+/// Plays round `i` (counting from 0), returning the new positions and whether any elf wanted
+/// to move.
+fn round(world: &HashSet<Pos>, i: usize) -> (HashSet<Pos>, bool) {
+    let mut new_world = HashMap::new();
+    let mut anything_moved = false;
+    for x in world {
+        if x.neighbours8().all(|p| !world.contains(&p)) {
+            new_world.insert(*x, *x);
+            continue;
+        }
+        let mut moved = false;
+        for j in 0..DIRECTIONS.len() {
+            let dir = &DIRECTIONS[(i + j) % DIRECTIONS.len()];
+            if !moved && !dir.iter().any(|d| world.contains(&(*x + d.delta()))) {
+                match new_world.entry(*x + dir[0].delta()) {
+                    Entry::Vacant(entry) => {
+                        entry.insert(*x);
+                    }
+                    Entry::Occupied(entry) => {
+                        let prev_pos = *entry.get();
+                        entry.remove();
+                        new_world.insert(prev_pos, prev_pos);
+                        new_world.insert(*x, *x);
+                    }
+                }
+                moved = true;
+            }
+        }
+        if !moved {
+            new_world.insert(*x, *x);
+        }
+        anything_moved = true;
+    }
+
+    (HashSet::from_iter(new_world.keys().copied()), anything_moved)
+}
+
+/// The elves spreading out, a round per step, until none of them wants to move.
+pub struct Spread {
+    elves: HashSet<Pos>,
+    round: usize,
+    done: bool,
+}
+
+impl Animation for Spread {
+    fn frame(&self) -> String {
+        let Some(bounds) = BBox2::from_points(&self.elves) else { return String::new() };
+        draw(&bounds, |p| if self.elves.contains(&p) { '#' } else { '.' })
+    }
+
+    fn step(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+        let (elves, anything_moved) = round(&self.elves, self.round);
+        self.elves = elves;
+        self.round += 1;
+        self.done = !anything_moved;
+        true
+    }
+
+    /// Elves in green on brown ground.
+    fn picture(&self) -> Option<Picture> {
+        let bounds = BBox2::from_points(&self.elves)?;
+        let palette = vec![[90, 60, 30], [60, 200, 60]];
+        Some(Picture::draw(&bounds, palette, |p| self.elves.contains(&p) as u8))
+    }
+}
+
+pub fn animation(elves: &HashSet<Pos>) -> Spread {
+    Spread { elves: elves.clone(), round: 0, done: false }
+}
+
+/// Where the elves end up, once none of them wants to move.
+pub fn picture(elves: &HashSet<Pos>) -> Picture {
+    let mut spread = animation(elves);
+    while spread.step() {}
+    spread.picture().expect("No elves")
+}
+
+params! {
+    pub struct Params {
+        /// Rounds played before the empty ground is counted in part 1.
+        rounds: usize = 11,
+    }
+}
+
+/// There are elves to spread out.
+pub fn validate(elves: &HashSet<Pos>) -> Result<(), Violation> {
+    if elves.is_empty() {
+        return Err(Violation::new(DAY, "at least one elf", "none"));
+    }
+    Ok(())
+}
+
+pub fn part1(elves: &HashSet<Pos>, params: &Params) -> usize {
+    let mut world = elves.clone();
+    for i in 0..params.rounds {
+        world = round(&world, i).0;
+    }
+    BBox2::from_points(&world).unwrap().area() as usize - world.len()
+}
+
+pub fn part2(elves: &HashSet<Pos>, _: &Params) -> usize {
+    let mut world = elves.clone();
+    for i in 0.. {
+        let (new_world, anything_moved) = round(&world, i);
+        if !anything_moved {
+            return i + 1;
+        }
+        world = new_world;
+    }
+    unreachable!()
+}
+
+pub fn solve(input: &str, params: &Params) -> Result<Answers<usize>, ParseError> {
+    Ok(Answers::solve(&parse(input)?, |e| part1(e, params), |e| part2(e, params)))
+}
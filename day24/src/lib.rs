@@ -0,0 +1,226 @@
+use aoc_common::animate::{draw, Animation};
+use aoc_common::parse::end_of_input;
+use aoc_common::search::{bfs, Found};
+use aoc_common::{params, verbose, Answers, BBox2, Dir4, Grid, ParseError, Point2, Violation};
+use std::collections::HashSet;
+
+const DAY: &str = "day24";
+
+pub type World = Grid<Loc>;
+
+/// `x` is the column, `y` the row. Rows past the bottom of the map continue on the way back up
+/// and then down again, so the three trips of step 2 form one long path.
+type Pos = Point2<i32>;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Loc {
+    Wall,
+    Blizzards(HashSet<Dir4>),
+}
+
+use Loc::*;
+
+fn to_loc(c: char) -> Option<Loc> {
+    match c {
+        '#' => Some(Wall),
+        '.' => Some(Blizzards(HashSet::new())),
+        _ => Dir4::from_arrow(c).map(|dir| Blizzards(HashSet::from([dir]))),
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+struct State {
+    pos: Pos,
+    time: usize,
+}
+
+/// Wraps a blizzard that hit the wall around to the other side of the basin.
+fn wrap(n: i32, size: usize) -> i32 {
+    if n == 0 {
+        size as i32 - 2
+    } else if n == size as i32 - 1 {
+        1
+    } else {
+        n
+    }
+}
+
+fn storm(world: &World) -> World {
+    let mut new_world = world.map(|l| if *l == Wall { Wall } else { Blizzards(HashSet::new()) });
+    for (p, loc) in world.iter::<i32>() {
+        if let Blizzards(directions) = loc {
+            for dir in directions {
+                let to = p + dir.delta();
+                let to = Pos { x: wrap(to.x, world.width()), y: wrap(to.y, world.height()) };
+                if let Blizzards(s) = &mut new_world[to] {
+                    s.insert(*dir);
+                }
+            }
+        }
+    }
+    new_world
+}
+
+fn moves(p: &Pos) -> impl Iterator<Item = Pos> {
+    let p = *p;
+    std::iter::once(p).chain(p.neighbours4().filter(|m| m.x >= 1 && m.y >= 1))
+}
+
+/// The basin with the blizzards at their starting positions.
+pub fn parse(input: &str) -> Result<World, ParseError> {
+    let world = Grid::try_parse(DAY, input, "`#`, `.` or a blizzard arrow", to_loc)?;
+    if !world.rows().next().is_some_and(|row| row.contains(&Blizzards(HashSet::new()))) {
+        return Err(end_of_input(DAY, input, "an opening in the top wall"));
+    }
+    Ok(world)
+}
+
+/// The basin is walled in, but for one opening in the top and one in the bottom wall, and no
+/// blizzard goes up or down the columns of the openings, where it would leave the basin.
+pub fn validate(world: &World) -> Result<(), Violation> {
+    let (width, height) = (world.width() as i32, world.height() as i32);
+    let mut openings = Vec::new();
+    for (p, loc) in world.iter::<i32>() {
+        let on_side = p.x == 0 || p.x == width - 1;
+        let on_end = p.y == 0 || p.y == height - 1;
+        match loc {
+            Wall => {}
+            Blizzards(_) if on_end && !on_side => openings.push(p),
+            Blizzards(_) if on_side || on_end => {
+                let found = format!("an opening at row {}, column {}", p.y + 1, p.x + 1);
+                return Err(Violation::new(DAY, "walls all around the basin", found));
+            }
+            Blizzards(_) => {}
+        }
+    }
+    for y in [0, height - 1] {
+        let count = openings.iter().filter(|p| p.y == y).count();
+        if count != 1 {
+            let wall = if y == 0 { "top" } else { "bottom" };
+            let assumption = format!("exactly one opening in the {wall} wall");
+            return Err(Violation::new(DAY, assumption, count.to_string()));
+        }
+    }
+    let up_or_down = |p: &Pos| match &world[*p] {
+        Blizzards(dirs) => dirs.contains(&Dir4::N) || dirs.contains(&Dir4::S),
+        Wall => false,
+    };
+    for opening in &openings {
+        let mut column = (1..height - 1).map(|y| Pos { x: opening.x, y });
+        if let Some(p) = column.find(up_or_down) {
+            let found = format!("one at row {}, column {}", p.y + 1, p.x + 1);
+            let assumption = "no blizzards going up or down the columns of the openings";
+            return Err(Violation::new(DAY, assumption, found));
+        }
+    }
+    Ok(())
+}
+
+/// The row of the basin that row `y` of the long path is on.
+fn fold(y: i32, height: i32) -> i32 {
+    if y < height {
+        y
+    } else if y < 2 * height - 1 {
+        2 * (height - 1) - y
+    } else {
+        y + 2 - 2 * height
+    }
+}
+
+/// Fastest way to cross the basin `trips` times, going back and forth.
+fn fastest(initial_world: &World, trips: u16) -> Found<State, usize> {
+    let world_height = initial_world.height() as i32;
+    let world_width = initial_world.width() as i32;
+    let goal = i32::from(trips) * (world_height - 1);
+
+    let origin = Pos {
+        x: initial_world.rows().next().unwrap().iter().position(|l| *l != Wall).unwrap() as i32,
+        y: 0,
+    };
+
+    let mut worlds = vec![initial_world.clone()];
+
+    let successors = |s: &State| {
+        while worlds.len() < s.time + 2 {
+            worlds.push(storm(worlds.last().unwrap()));
+        }
+        let world = &worlds[s.time + 1];
+
+        let mut next = Vec::new();
+        for m in moves(&s.pos) {
+            if m.x >= world_width {
+                continue;
+            }
+
+            match &world[Pos { x: m.x, y: fold(m.y, world_height) }] {
+                Blizzards(b) if b.is_empty() => next.push(State { pos: m, time: s.time + 1 }),
+                _ => {}
+            }
+        }
+        next
+    };
+    let found = bfs(State { pos: origin, time: 0 }, successors, |s| s.pos.y == goal)
+        .expect("No way through the basin");
+    verbose!("Search: {}", found.stats);
+    found
+}
+
+params! {
+    pub struct Params {
+        /// Crossings of the basin in part 1.
+        trips: u16 = 1,
+        /// Crossings in part 2, going back for the snacks.
+        trips_with_snacks: u16 = 3,
+    }
+}
+
+pub fn part1(world: &World, params: &Params) -> usize { fastest(world, params.trips).dist }
+
+pub fn part2(world: &World, params: &Params) -> usize {
+    fastest(world, params.trips_with_snacks).dist
+}
+
+/// The expedition going through the basin on the fastest path, a minute per step.
+pub struct Expedition {
+    world: World,
+    path: Vec<Pos>,
+    time: usize,
+}
+
+impl Animation for Expedition {
+    /// The walls as `#`, the expedition as `E` and the blizzards as their arrows, or their count
+    /// where several share a spot.
+    fn frame(&self) -> String {
+        let bounds = BBox2 {
+            min: Pos::origin(),
+            max: Pos { x: self.world.width() as i32 - 1, y: self.world.height() as i32 - 1 },
+        };
+        draw(&bounds, |p| match &self.world[p] {
+            _ if p == self.path[self.time] => 'E',
+            Wall => '#',
+            Blizzards(s) if s.len() > 1 => char::from_digit(s.len() as u32, 10).unwrap(),
+            Blizzards(s) => s.iter().next().map_or('.', |d| d.arrow()),
+        })
+    }
+
+    fn step(&mut self) -> bool {
+        if self.time + 1 == self.path.len() {
+            return false;
+        }
+        self.world = storm(&self.world);
+        self.time += 1;
+        true
+    }
+}
+
+/// The trips of part 2.
+pub fn animation(world: &World, params: &Params) -> Expedition {
+    let height = world.height() as i32;
+    let path = fastest(world, params.trips_with_snacks).path;
+    let path = path.iter().map(|s| Pos { x: s.pos.x, y: fold(s.pos.y, height) }).collect();
+    Expedition { world: world.clone(), path, time: 0 }
+}
+
+pub fn solve(input: &str, params: &Params) -> Result<Answers<usize>, ParseError> {
+    Ok(Answers::solve(&parse(input)?, |w| part1(w, params), |w| part2(w, params)))
+}
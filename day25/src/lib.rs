@@ -0,0 +1,160 @@
+use aoc_common::{Answers, Line, ParseError};
+
+const DAY: &str = "day25";
+
+/// Reads a SNAFU number, if it fits in an `i64`.
+fn decode(s: &str) -> Option<i64> {
+    let mut result: i64 = 0;
+    for c in s.chars() {
+        let digit = match c {
+            '=' => -2,
+            '-' => -1,
+            '0' => 0,
+            '1' => 1,
+            '2' => 2,
+            _ => panic!("Invalid character {}", c),
+        };
+        result = result.checked_mul(5)?.checked_add(digit)?;
+    }
+    Some(result)
+}
+
+/// Writes a positive number in SNAFU.
+pub fn encode(mut i: i64) -> String {
+    let mut result = Vec::new();
+    while i > 0 {
+        // Digits 3 and 4 are written as -2 and -1, carrying one to the next digit.
+        let (digit, carry) = match i % 5 {
+            0 => ('0', 0),
+            1 => ('1', 0),
+            2 => ('2', 0),
+            3 => ('=', 1),
+            4 => ('-', 1),
+            _ => panic!("Oops"),
+        };
+        result.push(digit);
+        i = i / 5 + carry;
+    }
+
+    result.reverse();
+    result.into_iter().collect()
+}
+
+/// The fuel requirements, decoded from SNAFU.
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    Line::all(DAY, input)
+        .map(|line| match line.text.chars().position(|c| !"=-012".contains(c)) {
+            Some(i) => Err(line.error_at_char(i, "a SNAFU digit")),
+            None => decode(line.text)
+                .ok_or_else(|| line.error(line.text, "a SNAFU number that fits in 64 bits")),
+        })
+        .collect()
+}
+
+pub fn part1(fuel: &[i64]) -> String { encode(fuel.iter().sum()) }
+
+/// There is no second puzzle on the last day.
+pub fn solve(input: &str) -> Result<Answers<String, ()>, ParseError> {
+    Ok(Answers { part1: part1(&parse(input)?), part2: () })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("1"), Some(1));
+        assert_eq!(decode("2"), Some(2));
+        assert_eq!(decode("1="), Some(3));
+        assert_eq!(decode("1-"), Some(4));
+        assert_eq!(decode("10"), Some(5));
+        assert_eq!(decode("11"), Some(6));
+        assert_eq!(decode("12"), Some(7));
+        assert_eq!(decode("2="), Some(8));
+        assert_eq!(decode("2-"), Some(9));
+        assert_eq!(decode("20"), Some(10));
+        assert_eq!(decode("1=0"), Some(15));
+        assert_eq!(decode("1-0"), Some(20));
+        assert_eq!(decode("1=11-2"), Some(2022));
+        assert_eq!(decode("1-0---0"), Some(12345));
+        assert_eq!(decode("1121-1110-1=0"), Some(314159265));
+
+        assert_eq!(decode("1=-0-2"), Some(1747));
+        assert_eq!(decode("12111"), Some(906));
+        assert_eq!(decode("2=0="), Some(198));
+        assert_eq!(decode("21"), Some(11));
+        assert_eq!(decode("2=01"), Some(201));
+        assert_eq!(decode("111"), Some(31));
+        assert_eq!(decode("20012"), Some(1257));
+        assert_eq!(decode("112"), Some(32));
+        assert_eq!(decode("1=-1="), Some(353));
+        assert_eq!(decode("1-12"), Some(107));
+        assert_eq!(decode("12"), Some(7));
+        assert_eq!(decode("1="), Some(3));
+        assert_eq!(decode("122"), Some(37));
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode(1), "1");
+        assert_eq!(encode(2), "2");
+        assert_eq!(encode(3), "1=");
+        assert_eq!(encode(4), "1-");
+        assert_eq!(encode(5), "10");
+        assert_eq!(encode(6), "11");
+        assert_eq!(encode(7), "12");
+        assert_eq!(encode(8), "2=");
+        assert_eq!(encode(9), "2-");
+        assert_eq!(encode(10), "20");
+        assert_eq!(encode(15), "1=0");
+        assert_eq!(encode(20), "1-0");
+        assert_eq!(encode(2022), "1=11-2");
+        assert_eq!(encode(12345), "1-0---0");
+        assert_eq!(encode(314159265), "1121-1110-1=0");
+
+        assert_eq!(encode(1747), "1=-0-2");
+        assert_eq!(encode(906), "12111");
+        assert_eq!(encode(198), "2=0=");
+        assert_eq!(encode(11), "21");
+        assert_eq!(encode(201), "2=01");
+        assert_eq!(encode(31), "111");
+        assert_eq!(encode(1257), "20012");
+        assert_eq!(encode(32), "112");
+        assert_eq!(encode(353), "1=-1=");
+        assert_eq!(encode(107), "1-12");
+        assert_eq!(encode(7), "12");
+        assert_eq!(encode(3), "1=");
+        assert_eq!(encode(37), "122");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse("1=-0-2\n12x11\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_deref()), (2, 3, Some("x")));
+        assert!(parse(&"2".repeat(28)).is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for i in [1, 4, 5, i64::MAX / 5 * 2, i64::MAX - 1, i64::MAX] {
+            assert_eq!(parse(&encode(i)).unwrap(), [i]);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_decode_encode(i in 1..=i64::MAX) {
+            let s = encode(i);
+            prop_assert!(s.starts_with(['1', '2']), "{} has a leading {}", s, &s[..1]);
+            prop_assert_eq!(decode(&s), Some(i));
+        }
+
+        #[test]
+        fn prop_encode_decode(s in "[12][=\\-012]{0,20}") {
+            // A leading 1 or 2 makes the number positive, and 21 digits always fit in an i64.
+            prop_assert_eq!(encode(decode(&s).unwrap()), s);
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use aoc_common::{params, Answers, Line, ParseError, Violation};
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::ops::BitAnd;
+
+const DAY: &str = "day3";
+
+/// A set of items, a bit per priority: `a` to `z` are 1 to 26, and `A` to `Z` 27 to 52.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Items(u64);
+
+impl Items {
+    /// Every item there is.
+    pub const ALL: Items = Items(((1 << 52) - 1) << 1);
+
+    pub fn len(self) -> u32 { self.0.count_ones() }
+
+    pub fn is_empty(self) -> bool { self.0 == 0 }
+
+    /// The priorities of the items, lowest first.
+    pub fn priorities(self) -> impl Iterator<Item = u32> {
+        (1..=52).filter(move |priority| self.0 & (1 << priority) != 0)
+    }
+}
+
+impl BitAnd for Items {
+    type Output = Items;
+
+    fn bitand(self, other: Items) -> Items { Items(self.0 & other.0) }
+}
+
+impl FromIterator<u8> for Items {
+    fn from_iter<I: IntoIterator<Item = u8>>(priorities: I) -> Items {
+        Items(priorities.into_iter().fold(0, |bits, priority| bits | 1 << priority))
+    }
+}
+
+/// The letters of the items, in order of priority.
+impl fmt::Display for Items {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for priority in self.priorities() {
+            let item = match priority {
+                1..=26 => b'a' + priority as u8 - 1,
+                _ => b'A' + priority as u8 - 27,
+            };
+            write!(f, "{}", item as char)?;
+        }
+        Ok(())
+    }
+}
+
+fn priority(item: char) -> Option<u8> {
+    match item {
+        'a'..='z' => Some(item as u8 - b'a' + 1),
+        'A'..='Z' => Some(item as u8 - b'A' + 27),
+        _ => None,
+    }
+}
+
+/// The items of a rucksack, in the order they are packed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rucksack {
+    priorities: Vec<u8>,
+    items: Items,
+}
+
+impl Rucksack {
+    pub fn len(&self) -> usize { self.priorities.len() }
+
+    pub fn is_empty(&self) -> bool { self.priorities.is_empty() }
+
+    pub fn items(&self) -> Items { self.items }
+
+    /// The items of each of `n` compartments of equal size, if there are items and they split
+    /// evenly.
+    pub fn compartments(&self, n: usize) -> Option<impl Iterator<Item = Items> + '_> {
+        if n == 0 || self.is_empty() || !self.len().is_multiple_of(n) {
+            return None;
+        }
+        Some(self.priorities.chunks(self.len() / n).map(|items| items.iter().copied().collect()))
+    }
+
+    /// The items in all of `n` compartments.
+    pub fn shared(&self, n: usize) -> Option<Items> {
+        Some(self.compartments(n)?.fold(Items::ALL, BitAnd::bitand))
+    }
+}
+
+/// The rucksacks, checked to hold some items, all from `a` to `z` and `A` to `Z`.
+pub fn parse(input: &str) -> Result<Vec<Rucksack>, ParseError> {
+    Line::all(DAY, input)
+        .map(|line| {
+            let expected = "an item from a to z or A to Z";
+            if line.text.is_empty() {
+                return Err(line.error("", expected));
+            }
+            let priorities = (line.text.chars().enumerate())
+                .map(|(i, c)| priority(c).ok_or_else(|| line.error_at_char(i, expected)))
+                .collect::<Result<Vec<u8>, _>>()?;
+            let items = priorities.iter().copied().collect();
+            Ok(Rucksack { priorities, items })
+        })
+        .collect()
+}
+
+params! {
+    pub struct Params {
+        /// Compartments of equal size in every rucksack.
+        compartments: NonZeroUsize = NonZeroUsize::new(2).unwrap(),
+        /// Rucksacks in a group of elves.
+        group: NonZeroUsize = NonZeroUsize::new(3).unwrap(),
+    }
+}
+
+/// A group of rucksacks and the items all of them hold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    /// The place of the group in the input, from 1.
+    pub index: usize,
+    /// The rucksacks of the group, by their place in the input, from 1.
+    pub rucksacks: std::ops::Range<usize>,
+    pub shared: Items,
+}
+
+impl fmt::Display for Group {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Group { index, rucksacks, shared } = self;
+        let (first, last) = (rucksacks.start, rucksacks.end - 1);
+        let priority: u32 = shared.priorities().sum();
+        write!(f, "Group {index} (rucksacks {first} to {last}): {shared} (priority {priority})")
+    }
+}
+
+/// The rucksacks in groups of `size` (the last one may be smaller), with what each group shares.
+pub fn groups(rucksacks: &[Rucksack], size: NonZeroUsize) -> Vec<Group> {
+    let size = size.get();
+    (rucksacks.chunks(size).enumerate())
+        .map(|(i, group)| Group {
+            index: i + 1,
+            rucksacks: i * size + 1..i * size + group.len() + 1,
+            shared: group.iter().map(Rucksack::items).fold(Items::ALL, BitAnd::bitand),
+        })
+        .collect()
+}
+
+/// Every rucksack splits into the compartments with exactly one item in all of them, and the
+/// rucksacks come in whole groups with exactly one item in all of a group's.
+pub fn validate(rucksacks: &[Rucksack], params: &Params) -> Result<(), Violation> {
+    let Params { compartments, group } = *params;
+    for (i, rucksack) in rucksacks.iter().enumerate() {
+        let Some(shared) = rucksack.shared(compartments.get()) else {
+            let assumption = format!("rucksacks splitting into {compartments} compartments");
+            let found = format!("{} items in rucksack {}", rucksack.len(), i + 1);
+            return Err(Violation::new(DAY, assumption, found));
+        };
+        if shared.len() != 1 {
+            let found = format!("{} in rucksack {}", shared.len(), i + 1);
+            return Err(Violation::new(DAY, "exactly one item in all compartments", found));
+        }
+    }
+    if !rucksacks.len().is_multiple_of(group.get()) {
+        let found = format!("{} rucksacks", rucksacks.len());
+        return Err(Violation::new(DAY, format!("groups of {group} rucksacks"), found));
+    }
+    for group in groups(rucksacks, group) {
+        if group.shared.len() != 1 {
+            let found = format!("{} in group {}", group.shared.len(), group.index);
+            return Err(Violation::new(DAY, "exactly one item common to each group", found));
+        }
+    }
+    Ok(())
+}
+
+/// Rucksacks that don't split into the compartments share nothing; `validate` reports them.
+pub fn part1(rucksacks: &[Rucksack], params: &Params) -> u32 {
+    let compartments = params.compartments.get();
+    (rucksacks.iter().filter_map(|rucksack| rucksack.shared(compartments)))
+        .flat_map(Items::priorities)
+        .sum()
+}
+
+pub fn part2(rucksacks: &[Rucksack], params: &Params) -> u32 {
+    groups(rucksacks, params.group).iter().flat_map(|group| group.shared.priorities()).sum()
+}
+
+pub fn solve(input: &str, params: &Params) -> Result<Answers<u32>, ParseError> {
+    Ok(Answers::solve(parse(input)?.as_slice(), |r| part1(r, params), |r| part2(r, params)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::params::Params as _;
+
+    fn params(compartments: usize, group: usize) -> Params {
+        let n = |n| NonZeroUsize::new(n).unwrap();
+        Params { compartments: n(compartments), group: n(group) }
+    }
+
+    const INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n\
+                         PmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\n\
+                         ttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw\n";
+
+    #[test]
+    fn test_items() {
+        let items: Items = [1, 16, 52].into_iter().collect();
+        assert_eq!(items.to_string(), "apZ");
+        assert_eq!(items.priorities().collect::<Vec<_>>(), [1, 16, 52]);
+        assert_eq!(Items::ALL.len(), 52);
+        assert!((items & Items::default()).is_empty());
+    }
+
+    #[test]
+    fn test_compartments() {
+        let rucksacks = parse(INPUT).unwrap();
+        let shared: Vec<String> =
+            rucksacks.iter().map(|r| r.shared(2).unwrap().to_string()).collect();
+        assert_eq!(shared, ["p", "L", "P", "v", "t", "s"]);
+        assert_eq!(part1(&rucksacks, &Params::puzzle()), 157);
+        assert_eq!(parse("abcab\n").unwrap()[0].shared(2), None);
+        assert_eq!(parse("abxaby\n").unwrap()[0].shared(3).unwrap().to_string(), "");
+        assert_eq!(parse("abaaba\n").unwrap()[0].shared(3).unwrap().to_string(), "a");
+    }
+
+    #[test]
+    fn test_groups() {
+        let rucksacks = parse(INPUT).unwrap();
+        let groups = groups(&rucksacks, params(2, 3).group);
+        assert_eq!(groups[0].to_string(), "Group 1 (rucksacks 1 to 3): r (priority 18)");
+        assert_eq!(groups[1].to_string(), "Group 2 (rucksacks 4 to 6): Z (priority 52)");
+        assert_eq!(part2(&rucksacks, &Params::puzzle()), 70);
+        assert_eq!(part2(&rucksacks, &params(2, 6)), 0);
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = parse("abc\nab-d\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_deref()), (2, 3, Some("-")));
+        let rucksacks = parse("abcab\nabca\nabca\n").unwrap();
+        let violation = validate(&rucksacks, &Params::puzzle()).unwrap_err();
+        assert_eq!(violation.found, "5 items in rucksack 1");
+        let rucksacks = parse("abca\nxaya\n").unwrap();
+        let violation = validate(&rucksacks, &Params::puzzle()).unwrap_err();
+        assert_eq!(violation.assumption, "groups of 3 rucksacks");
+        assert!(validate(&rucksacks, &params(2, 2)).is_ok());
+        assert_eq!(part1(&parse("abca\nabcab\n").unwrap(), &Params::puzzle()), 1);
+        assert_eq!(part1(&parse("abca\nabcabc\n").unwrap(), &params(4, 1)), 0);
+    }
+
+    #[test]
+    fn test_empty_line() {
+        let err = parse(&format!("{INPUT}\n")).unwrap_err();
+        assert_eq!((err.line, err.column, err.found), (7, 1, None));
+        let err = parse("ab\n\nab\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn test_params() {
+        let set = |name, value| Params::puzzle().set(name, value);
+        assert!(set("group", "0").unwrap_err().starts_with("Invalid value 0 for --group"));
+        assert!(set("compartments", "0").is_err());
+        assert!(set("group", "4").is_ok());
+    }
+}
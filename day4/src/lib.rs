@@ -0,0 +1,55 @@
+use aoc_common::{Answers, Line, ParseError, Violation};
+use std::ops::RangeInclusive;
+
+const DAY: &str = "day4";
+
+/// The sections assigned to a pair of elves.
+pub type Pair = (RangeInclusive<i32>, RangeInclusive<i32>);
+
+fn parse_range(line: Line, s: &str) -> Result<RangeInclusive<i32>, ParseError> {
+    let (start, end) = s.split_once('-').ok_or_else(|| line.error(s, "a range like `2-4`"))?;
+    Ok(line.parse(start, "a section number")?..=line.parse(end, "a section number")?)
+}
+
+fn parse_line(line: Line) -> Result<Pair, ParseError> {
+    let (elf1, elf2) = line.text.split_once(',').ok_or_else(|| line.error("", "`,`"))?;
+    Ok((parse_range(line, elf1)?, parse_range(line, elf2)?))
+}
+
+pub fn parse(input: &str) -> Result<Vec<Pair>, ParseError> {
+    Line::all(DAY, input).map(parse_line).collect()
+}
+
+/// No range ends before it starts.
+pub fn validate(pairs: &[Pair]) -> Result<(), Violation> {
+    for (i, (e1, e2)) in pairs.iter().enumerate() {
+        if let Some(range) = [e1, e2].into_iter().find(|r| r.is_empty()) {
+            let found = format!("{}-{} in pair {}", range.start(), range.end(), i + 1);
+            return Err(Violation::new(DAY, "ranges that end after they start", found));
+        }
+    }
+    Ok(())
+}
+
+pub fn part1(pairs: &[Pair]) -> usize {
+    pairs
+        .iter()
+        .filter(|(e1, e2)| {
+            (e1.contains(e2.start()) && e1.contains(e2.end()))
+                || (e2.contains(e1.start()) && e2.contains(e1.end()))
+        })
+        .count()
+}
+
+pub fn part2(pairs: &[Pair]) -> usize {
+    pairs
+        .iter()
+        .filter(|(e1, e2)| {
+            e1.contains(e2.start()) || e1.contains(e2.end()) || e2.contains(e1.start())
+        })
+        .count()
+}
+
+pub fn solve(input: &str) -> Result<Answers<usize>, ParseError> {
+    Ok(Answers::solve(parse(input)?.as_slice(), part1, part2))
+}
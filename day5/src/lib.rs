@@ -0,0 +1,108 @@
+use aoc_common::{Answers, Line, ParseError, Violation};
+use regex::Regex;
+
+const DAY: &str = "day5";
+
+/// Crates in each stack, bottom first.
+pub type State = Vec<Vec<char>>;
+
+pub struct Move {
+    pub count: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+pub fn parse(input: &str) -> Result<(State, Vec<Move>), ParseError> {
+    let stack_row_re = Regex::new(r"((\[(?P<crate>\w)\]|   ) ?)").unwrap();
+    let move_re = Regex::new(r"move (?P<count>\d+) from (?P<from>\d+) to (?P<to>\d+)").unwrap();
+
+    // 10 should be enough stacks for anyone!
+    let mut state: State = vec![vec![]; 10];
+
+    let mut lines = Line::all(DAY, input);
+
+    // First parse initial state.
+    for line in lines.by_ref() {
+        if line.text.is_empty() {
+            break;
+        }
+
+        for (i, group) in stack_row_re.captures_iter(line.text).enumerate() {
+            if let Some(c) = &group.name("crate") {
+                if i >= state.len() {
+                    return Err(line.error(c.as_str(), format!("at most {} stacks", state.len())));
+                }
+                state[i].push(c.as_str().chars().next().unwrap());
+            }
+        }
+    }
+    for line in &mut state {
+        line.reverse();
+    }
+
+    // Parse moves.
+    let mut moves = Vec::new();
+    for line in lines.filter(|line| !line.text.is_empty()) {
+        let group = move_re
+            .captures(line.text)
+            .ok_or_else(|| line.error(line.text, "a move like `move 1 from 2 to 1`"))?;
+        let stack = |name| {
+            let token = group.name(name).unwrap().as_str();
+            match line.parse(token, "a stack number")? {
+                n if (1..=state.len()).contains(&n) => Ok(n),
+                _ => Err(line.error(token, format!("a stack number from 1 to {}", state.len()))),
+            }
+        };
+        moves.push(Move {
+            count: line.parse(group.name("count").unwrap().as_str(), "a number of crates")?,
+            from: stack("from")?,
+            to: stack("to")?,
+        });
+    }
+
+    Ok((state, moves))
+}
+
+/// No move takes more crates than its stack has at that point.
+pub fn validate((state, moves): &(State, Vec<Move>)) -> Result<(), Violation> {
+    let mut heights: Vec<usize> = state.iter().map(Vec::len).collect();
+    for (i, m) in moves.iter().enumerate() {
+        if heights[m.from - 1] < m.count {
+            let (count, from, height) = (m.count, m.from, heights[m.from - 1]);
+            let found =
+                format!("move {} taking {count} from stack {from}, which has {height}", i + 1);
+            return Err(Violation::new(DAY, "moves taking only the crates there are", found));
+        }
+        heights[m.from - 1] -= m.count;
+        heights[m.to - 1] += m.count;
+    }
+    Ok(())
+}
+
+fn tops(state: &State) -> String { state.iter().filter_map(|stack| stack.last()).collect() }
+
+pub fn part1((state, moves): &(State, Vec<Move>)) -> String {
+    let mut state9000 = state.clone();
+    for m in moves {
+        for _ in 0..m.count {
+            let c = state9000[m.from - 1].pop().unwrap();
+            state9000[m.to - 1].push(c);
+        }
+    }
+    tops(&state9000)
+}
+
+pub fn part2((state, moves): &(State, Vec<Move>)) -> String {
+    let mut state9001 = state.clone();
+    for m in moves {
+        let from_stack = &mut state9001[m.from - 1];
+        let offset = from_stack.len() - m.count;
+        let mut c = from_stack.drain(offset..).collect();
+        state9001[m.to - 1].append(&mut c);
+    }
+    tops(&state9001)
+}
+
+pub fn solve(input: &str) -> Result<Answers<String>, ParseError> {
+    Ok(Answers::solve(&parse(input)?, part1, part2))
+}
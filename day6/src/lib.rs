@@ -0,0 +1,58 @@
+use aoc_common::{params, Answers, ParseError, Violation};
+use std::collections::HashSet;
+use std::num::NonZeroUsize;
+
+const DAY: &str = "day6";
+
+pub fn marker_pos(s: &str, marker_len: NonZeroUsize) -> usize {
+    let marker_len = marker_len.get();
+    for (i, seq) in s.chars().collect::<Vec<char>>().windows(marker_len).enumerate() {
+        let set: HashSet<&char> = HashSet::from_iter(seq);
+        if set.len() == marker_len {
+            return i + marker_len;
+        }
+    }
+    0
+}
+
+/// The datastreams, one per line.
+pub fn parse(input: &str) -> Result<Vec<&str>, ParseError> { Ok(input.lines().collect()) }
+
+/// Marker positions of every datastream, comma separated.
+fn marker_positions(streams: &[&str], marker_len: NonZeroUsize) -> String {
+    streams.iter().map(|s| marker_pos(s, marker_len).to_string()).collect::<Vec<_>>().join(",")
+}
+
+params! {
+    pub struct Params {
+        /// Different characters in a start-of-packet marker.
+        packet: NonZeroUsize = NonZeroUsize::new(4).unwrap(),
+        /// Different characters in a start-of-message marker.
+        message: NonZeroUsize = NonZeroUsize::new(14).unwrap(),
+    }
+}
+
+/// Every datastream has both markers.
+pub fn validate(streams: &[&str], params: &Params) -> Result<(), Violation> {
+    for (i, stream) in streams.iter().enumerate() {
+        for (kind, len) in [("packet", params.packet), ("message", params.message)] {
+            if marker_pos(stream, len) == 0 {
+                let assumption = format!("a start-of-{kind} marker in every datastream");
+                return Err(Violation::new(DAY, assumption, format!("none in line {}", i + 1)));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn part1(streams: &[&str], params: &Params) -> String {
+    marker_positions(streams, params.packet)
+}
+
+pub fn part2(streams: &[&str], params: &Params) -> String {
+    marker_positions(streams, params.message)
+}
+
+pub fn solve(input: &str, params: &Params) -> Result<Answers<String>, ParseError> {
+    Ok(Answers::solve(parse(input)?.as_slice(), |s| part1(s, params), |s| part2(s, params)))
+}
@@ -1,21 +1,24 @@
-use std::collections::HashSet;
-use std::io::{self, BufRead};
-
-fn marker_pos(s: &str, marker_len: usize) -> i32 {
-    for (i, seq) in s.chars().collect::<Vec<char>>().windows(marker_len).enumerate() {
-        let set: HashSet<&char> = HashSet::from_iter(seq);
-        if set.len() == marker_len {
-            return (i + marker_len).try_into().unwrap();
-        }
-    }
-    return 0;
-}
+use aoc_common::{input, params, verbose};
+use std::process::exit;
 
 fn main() {
-    for line in io::stdin().lock().lines() {
-        let line = line.unwrap();
-        println!("Line: {}", line);
-        println!("Step 1: position {}", marker_pos(&line, 4));
-        println!("Step 2: position {}", marker_pos(&line, 14));
+    // `-v` echoes every datastream before its markers.
+    let args: params::Args<day6::Params> = params::args();
+    let input = input::read(&args.input).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        exit(1)
+    });
+    let lines = day6::parse(&input).unwrap_or_else(|e| {
+        eprintln!("{}", e.report(&input));
+        exit(1)
+    });
+    if let Err(violation) = day6::validate(&lines, &args.params) {
+        eprintln!("{violation}");
+        exit(1)
+    }
+    for line in lines {
+        verbose!("Line: {}", line);
+        println!("Step 1: position {}", day6::marker_pos(line, args.params.packet));
+        println!("Step 2: position {}", day6::marker_pos(line, args.params.message));
     }
 }
@@ -0,0 +1,89 @@
+use aoc_common::{params, Answers, Line, ParseError, Violation};
+use std::collections::HashMap;
+
+const DAY: &str = "day7";
+
+/// Total size of every directory, keyed by path.
+pub fn parse(input: &str) -> Result<HashMap<String, i32>, ParseError> {
+    let mut cur_dir = vec![];
+    let mut sizes = HashMap::new();
+    for line in Line::all(DAY, input) {
+        let parts: Vec<&str> = line.text.split(' ').collect();
+        if parts[0] == "$" {
+            match parts.get(1) {
+                Some(&"cd") => {
+                    let dir = line.require(parts.get(2), "a directory")?;
+                    if *dir == "/" {
+                        cur_dir.clear();
+                    } else if *dir == ".." {
+                        cur_dir.pop();
+                    } else {
+                        cur_dir.push(String::from(*dir));
+                    }
+                }
+                Some(&"ls") => {}
+                Some(command) => return Err(line.error(command, "`cd` or `ls`")),
+                None => return Err(line.error("", "`cd` or `ls`")),
+            }
+        } else if parts[0] == "dir" {
+            // Will be traversed later.
+        } else {
+            let size: i32 = line.parse(parts[0], "`$`, `dir` or a file size")?;
+
+            let mut add = |dir: String| {
+                let total: &mut i32 = sizes.entry(dir).or_insert(0);
+                *total = total
+                    .checked_add(size)
+                    .ok_or_else(|| line.error(parts[0], "sizes adding up to at most 2^31 - 1"))?;
+                Ok(())
+            };
+            add("/".to_string())?;
+            let mut trav_dir = String::new();
+            for dir in &cur_dir {
+                trav_dir += &("/".to_owned() + dir);
+                add(trav_dir.to_string())?;
+            }
+        }
+    }
+    Ok(sizes)
+}
+
+params! {
+    pub struct Params {
+        /// Largest directory counted in part 1.
+        small: i32 = 100000,
+        /// Size of the disk.
+        disk: i32 = 70000000,
+        /// Free space needed for the update.
+        needed: i32 = 30000000,
+    }
+}
+
+/// The files fit on the disk, and so does the update.
+pub fn validate(sizes: &HashMap<String, i32>, params: &Params) -> Result<(), Violation> {
+    let used = sizes.get("/").copied().unwrap_or(0);
+    if used > params.disk {
+        let found = format!("{used} used of {}", params.disk);
+        return Err(Violation::new(DAY, "files fitting on the disk", found));
+    }
+    if params.needed > params.disk {
+        let found = format!("{} needed of {}", params.needed, params.disk);
+        return Err(Violation::new(DAY, "an update fitting on the disk", found));
+    }
+    Ok(())
+}
+
+pub fn part1(sizes: &HashMap<String, i32>, params: &Params) -> i32 {
+    sizes.values().filter(|size| **size <= params.small).sum()
+}
+
+pub fn part2(sizes: &HashMap<String, i32>, params: &Params) -> i32 {
+    let free_space = params.disk - sizes.get("/").unwrap();
+    let space_needed = params.needed - free_space;
+
+    sizes.values().copied().filter(|size| *size > space_needed).min().unwrap_or(i32::MAX)
+}
+
+pub fn solve(input: &str, params: &Params) -> Result<Answers<i32>, ParseError> {
+    Ok(Answers::solve(&parse(input)?, |s| part1(s, params), |s| part2(s, params)))
+}
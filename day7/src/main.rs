@@ -1,52 +1,24 @@
-use std::collections::HashMap;
-use std::io::{self, BufRead};
+use aoc_common::{input, params};
+use std::process::exit;
 
 fn main() {
-    let mut cur_dir = vec![];
-    let mut sizes = HashMap::new();
-    for line in io::stdin().lock().lines() {
-        let line = line.unwrap();
-        let parts: Vec<&str> = line.split(" ").collect();
-        if parts[0] == "$" {
-            if parts[1] == "cd" {
-                if parts[2] == "/" {
-                    cur_dir.clear();
-                } else if parts[2] == ".." {
-                    cur_dir.pop();
-                } else {
-                    cur_dir.push(String::from(parts[2]));
-                }
-            }
-            if parts[1] == "ls" {}
-        } else {
-            if parts[0] == "dir" {
-                // Will be traversed later.
-            } else {
-                let size: i32 = parts[0].parse().unwrap();
-
-                *sizes.entry("/".to_string()).or_insert(0) += size;
-                let mut trav_dir = String::new();
-                for dir in &cur_dir {
-                    trav_dir += &("/".to_owned() + dir);
-                    *sizes.entry(trav_dir.to_string()).or_insert(0) += size;
-                }
-            }
-        }
-    }
-
-    let free_space = 70000000 - sizes.get("/").unwrap();
-    let space_needed = 30000000 - free_space;
-
-    let mut step1 = 0;
-    let mut step2 = i32::MAX;
-    for (_name, size) in &sizes {
-        if *size <= 100000 {
-            step1 += size;
-        }
-        if *size > space_needed {
-            step2 = *size.min(&step2);
-        }
+    let args = params::args();
+    let input = input::read(&args.input).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        exit(1)
+    });
+    let sizes = day7::parse(&input).unwrap_or_else(|e| {
+        eprintln!("{}", e.report(&input));
+        exit(1)
+    });
+    if let Err(violation) = day7::validate(&sizes, &args.params) {
+        eprintln!("{violation}");
+        exit(1)
     }
-    println!("Step 1: {}", step1);
-    println!("Step 2: {}", step2);
+    let answers = day7::solve(&input, &args.params).unwrap_or_else(|e| {
+        eprintln!("{}", e.report(&input));
+        exit(1)
+    });
+    println!("Step 1: {}", answers.part1);
+    println!("Step 2: {}", answers.part2);
 }
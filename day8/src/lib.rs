@@ -0,0 +1,127 @@
+use aoc_common::picture::{shades, Picture};
+use aoc_common::{Answers, BBox2, Grid, ParseError, Point2};
+
+const DAY: &str = "day8";
+
+/// Tree heights, row by row.
+pub type Field = Vec<Vec<i32>>;
+
+pub fn parse(input: &str) -> Result<Field, ParseError> {
+    let grid = Grid::try_parse(DAY, input, "a tree height", |c| c.to_digit(10).map(|d| d as i32))?;
+    Ok(grid.rows().map(|row| row.to_vec()).collect())
+}
+
+/// Which trees can be seen from outside the grid.
+fn visibility(field: &Field) -> Vec<Vec<bool>> {
+    let nrows = field.len();
+    let ncols = field[0].len();
+
+    let mut visible = vec![vec![false; ncols]; nrows];
+
+    for row in 0..nrows {
+        let mut lhighest = -1;
+        let mut rhighest = -1;
+        for col in 0..ncols {
+            // Pass from left to right.
+            if field[row][col] > lhighest {
+                visible[row][col] = true;
+                lhighest = field[row][col];
+            }
+
+            // Pass from right to left.
+            let rcol = ncols - 1 - col;
+            if field[row][rcol] > rhighest {
+                visible[row][rcol] = true;
+                rhighest = field[row][rcol];
+            }
+        }
+    }
+    for col in 0..ncols {
+        let mut dhighest = -1;
+        let mut uhighest = -1;
+        for row in 0..nrows {
+            // Down.
+            if field[row][col] > dhighest {
+                visible[row][col] = true;
+                dhighest = field[row][col];
+            }
+            // Up.
+            let urow = nrows - 1 - row;
+            if field[urow][col] > uhighest {
+                visible[urow][col] = true;
+                uhighest = field[urow][col];
+            }
+        }
+    }
+
+    visible
+}
+
+pub fn part1(field: &Field) -> usize {
+    visibility(field).iter().map(|row| row.iter().filter(|v| **v).count()).sum()
+}
+
+pub fn part2(field: &Field) -> usize {
+    let nrows = field.len();
+    let ncols = field[0].len();
+
+    let mut scenic = vec![vec![1; ncols]; nrows];
+
+    for row in 0..nrows {
+        let mut lheight2pos: Vec<usize> = vec![0; 10];
+        let mut rheight2pos: Vec<usize> = vec![0; 10];
+        for col in 0..ncols {
+            // Pass from left to right.
+            scenic[row][col] *= col - lheight2pos[field[row][col] as usize];
+            for i in 0..=field[row][col] {
+                lheight2pos[i as usize] = col;
+            }
+
+            // Pass from right to left.
+            let rcol = ncols - 1 - col;
+            scenic[row][rcol] *= col - rheight2pos[field[row][rcol] as usize];
+            for i in 0..=field[row][rcol] {
+                rheight2pos[i as usize] = col;
+            }
+        }
+    }
+    for col in 0..ncols {
+        let mut dheight2pos: Vec<usize> = vec![0; 10];
+        let mut uheight2pos: Vec<usize> = vec![0; 10];
+        for row in 0..nrows {
+            // Down.
+            scenic[row][col] *= row - dheight2pos[field[row][col] as usize];
+            for i in 0..=field[row][col] {
+                dheight2pos[i as usize] = row;
+            }
+
+            // Up.
+            let urow = nrows - 1 - row;
+            scenic[urow][col] *= row - uheight2pos[field[urow][col] as usize];
+            for i in 0..=field[urow][col] {
+                uheight2pos[i as usize] = row;
+            }
+        }
+    }
+
+    *scenic.iter().map(|row| row.iter().max().unwrap()).max().unwrap()
+}
+
+/// The trees by height, the hidden ones in greys and those visible from outside in greens.
+pub fn picture(field: &Field) -> Picture {
+    let visible = visibility(field);
+    let bounds = BBox2 {
+        min: Point2::origin(),
+        max: Point2 { x: field[0].len() as i32 - 1, y: field.len() as i32 - 1 },
+    };
+    let mut palette = shades([40, 40, 40], [160, 160, 160], 10);
+    palette.extend(shades([20, 90, 20], [140, 240, 90], 10));
+    Picture::draw(&bounds, palette, |p| {
+        let (x, y) = (p.x as usize, p.y as usize);
+        field[y][x] as u8 + if visible[y][x] { 10 } else { 0 }
+    })
+}
+
+pub fn solve(input: &str) -> Result<Answers<usize>, ParseError> {
+    Ok(Answers::solve(&parse(input)?, part1, part2))
+}
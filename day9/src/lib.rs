@@ -0,0 +1,211 @@
+use aoc_common::animate::{draw, Animation};
+use aoc_common::{params, Answers, BBox2, Dir4, Line, ParseError, Point2, Violation};
+use std::collections::HashSet;
+
+type Pos = Point2<i32>;
+
+fn follow(head: &Pos, tail: Pos) -> Pos {
+    if head.chebyshev(&tail) > 1 {
+        tail.step_towards(head)
+    } else {
+        tail
+    }
+}
+
+/// Moves the head of the rope some distance in a direction.
+pub type Motion = (Dir4, usize);
+
+const DAY: &str = "day9";
+
+fn parse_dir(dir: &str) -> Option<Dir4> {
+    match dir {
+        "R" => Some(Dir4::E),
+        "L" => Some(Dir4::W),
+        "U" => Some(Dir4::N),
+        "D" => Some(Dir4::S),
+        _ => None,
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Motion>, ParseError> {
+    Line::all(DAY, input)
+        .map(|line| {
+            let mut parts = line.text.split_whitespace();
+            let dir = line.require(parts.next(), "a direction")?;
+            let dir = parse_dir(dir).ok_or_else(|| line.error(dir, "R, L, U or D"))?;
+            let distance = line.require(parts.next(), "a distance")?;
+            Ok((dir, line.parse(distance, "a distance")?))
+        })
+        .collect()
+}
+
+/// A rope whose head goes through the motions, one step at a time.
+pub struct Rope {
+    motions: Vec<Motion>,
+    /// The motion being done, and how far the head moved in it so far.
+    motion: usize,
+    moved: usize,
+    knots: Vec<Pos>,
+    visited: HashSet<Pos>,
+}
+
+impl Rope {
+    pub fn new(motions: &[Motion], len: usize) -> Rope {
+        let knots = vec![Pos::origin(); len];
+        let visited = HashSet::from([*knots.last().unwrap()]);
+        Rope { motions: motions.to_vec(), motion: 0, moved: 0, knots, visited }
+    }
+}
+
+impl Animation for Rope {
+    /// The visited positions as `#`, the start as `s` and the knots from `H` to the tail, the
+    /// later ones hidden under the earlier.
+    fn frame(&self) -> String {
+        let bounds = BBox2::from_points(self.visited.iter().chain(&self.knots)).unwrap();
+        draw(&bounds.expand(1), |p| match self.knots.iter().position(|k| *k == p) {
+            Some(0) => 'H',
+            Some(i) => char::from_digit(i as u32 % 36, 36).unwrap(),
+            None if p == Pos::origin() => 's',
+            None if self.visited.contains(&p) => '#',
+            None => '.',
+        })
+    }
+
+    fn step(&mut self) -> bool {
+        while self.motions.get(self.motion).is_some_and(|&(_, distance)| self.moved == distance) {
+            self.motion += 1;
+            self.moved = 0;
+        }
+        let Some(&(direction, _)) = self.motions.get(self.motion) else { return false };
+        self.moved += 1;
+        self.knots[0] += direction.delta();
+        for i in 1..self.knots.len() {
+            self.knots[i] = follow(&self.knots[i - 1], self.knots[i]);
+        }
+        self.visited.insert(*self.knots.last().unwrap());
+        true
+    }
+}
+
+/// Number of positions visited by the tail of a rope with `len` knots.
+fn tail_visits(motions: &[Motion], len: usize) -> usize {
+    let mut rope = Rope::new(motions, len);
+    while rope.step() {}
+    rope.visited.len()
+}
+
+params! {
+    pub struct Params {
+        /// Knots of the rope in part 1.
+        short: usize = 2,
+        /// Knots of the rope in part 2.
+        long: usize = 10,
+    }
+}
+
+/// Both ropes have a knot.
+pub fn validate(_: &[Motion], params: &Params) -> Result<(), Violation> {
+    if params.short == 0 || params.long == 0 {
+        let found = format!("{} and {}", params.short, params.long);
+        return Err(Violation::new(DAY, "ropes of at least one knot", found));
+    }
+    Ok(())
+}
+
+pub fn part1(motions: &[Motion], params: &Params) -> usize { tail_visits(motions, params.short) }
+
+pub fn part2(motions: &[Motion], params: &Params) -> usize { tail_visits(motions, params.long) }
+
+/// The rope of part 2.
+pub fn animation(motions: &[Motion], params: &Params) -> Rope { Rope::new(motions, params.long) }
+
+pub fn solve(input: &str, params: &Params) -> Result<Answers<usize>, ParseError> {
+    Ok(Answers::solve(parse(input)?.as_slice(), |m| part1(m, params), |m| part2(m, params)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Dir8;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_follow_same_pos() {
+        assert_eq!(follow(&Pos { x: 2, y: 2 }, Pos { x: 2, y: 2 }), Pos { x: 2, y: 2 });
+    }
+
+    #[test]
+    fn test_follow_touching() {
+        assert_eq!(follow(&Pos { x: 2, y: 2 }, Pos { x: 3, y: 2 }), Pos { x: 3, y: 2 });
+        assert_eq!(follow(&Pos { x: 2, y: 2 }, Pos { x: 1, y: 2 }), Pos { x: 1, y: 2 });
+        assert_eq!(follow(&Pos { x: 2, y: 2 }, Pos { x: 2, y: 3 }), Pos { x: 2, y: 3 });
+        assert_eq!(follow(&Pos { x: 2, y: 2 }, Pos { x: 2, y: 1 }), Pos { x: 2, y: 1 });
+        assert_eq!(follow(&Pos { x: 2, y: 2 }, Pos { x: 3, y: 3 }), Pos { x: 3, y: 3 });
+        assert_eq!(follow(&Pos { x: 2, y: 2 }, Pos { x: 1, y: 1 }), Pos { x: 1, y: 1 });
+        assert_eq!(follow(&Pos { x: 2, y: 2 }, Pos { x: 1, y: 3 }), Pos { x: 1, y: 3 });
+        assert_eq!(follow(&Pos { x: 2, y: 2 }, Pos { x: 3, y: 1 }), Pos { x: 3, y: 1 });
+    }
+
+    #[test]
+    fn test_follow_straight() {
+        assert_eq!(follow(&Pos { x: 2, y: 2 }, Pos { x: 4, y: 2 }), Pos { x: 3, y: 2 });
+        assert_eq!(follow(&Pos { x: 2, y: 2 }, Pos { x: 0, y: 2 }), Pos { x: 1, y: 2 });
+        assert_eq!(follow(&Pos { x: 2, y: 2 }, Pos { x: 2, y: 4 }), Pos { x: 2, y: 3 });
+        assert_eq!(follow(&Pos { x: 2, y: 2 }, Pos { x: 2, y: 0 }), Pos { x: 2, y: 1 });
+    }
+
+    #[test]
+    fn test_rope_frame() {
+        let mut rope = Rope::new(&[(Dir4::E, 2)], 2);
+        assert!(rope.step());
+        assert!(rope.step());
+        assert!(!rope.step());
+        assert_eq!(rope.frame(), ".....\n.s1H.\n.....\n");
+    }
+
+    #[test]
+    fn test_follow_diag() {
+        assert_eq!(follow(&Pos { x: 2, y: 2 }, Pos { x: 4, y: 3 }), Pos { x: 3, y: 2 });
+        assert_eq!(follow(&Pos { x: 2, y: 2 }, Pos { x: 3, y: 4 }), Pos { x: 2, y: 3 });
+        assert_eq!(follow(&Pos { x: 2, y: 2 }, Pos { x: 0, y: 1 }), Pos { x: 1, y: 2 });
+        assert_eq!(follow(&Pos { x: 2, y: 2 }, Pos { x: 1, y: 0 }), Pos { x: 2, y: 1 });
+    }
+
+    proptest! {
+        #[test]
+        fn prop_follow_stays_adjacent(tail in 0..9usize, head_move in 0..8usize) {
+            // The tail starts on or next to the head, which then moves a step in any direction.
+            let head = Pos::origin();
+            let tail = head + Dir8::ALL.get(tail).map_or(Pos::origin(), |d| d.delta());
+            let head = head + Dir8::ALL[head_move].delta();
+            let followed = follow(&head, tail);
+            prop_assert!(followed.chebyshev(&head) <= 1);
+            prop_assert!(followed.chebyshev(&tail) <= 1);
+        }
+
+        #[test]
+        fn prop_rope_stays_together(
+            motions in prop::collection::vec((0..4usize, 1..6usize), 0..30),
+            len in 1..12usize,
+        ) {
+            let motions: Vec<Motion> =
+                motions.into_iter().map(|(d, n)| (Dir4::ALL[d], n)).collect();
+            let mut rope = Rope::new(&motions, len);
+            let mut steps = 0;
+            loop {
+                let before = rope.knots.clone();
+                if !rope.step() {
+                    break;
+                }
+                steps += 1;
+                for (knot, last) in rope.knots.iter().zip(&before) {
+                    prop_assert!(knot.chebyshev(last) <= 1);
+                }
+                for pair in rope.knots.windows(2) {
+                    prop_assert!(pair[0].chebyshev(&pair[1]) <= 1);
+                }
+            }
+            prop_assert_eq!(steps, motions.iter().map(|(_, n)| n).sum::<usize>());
+        }
+    }
+}
@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fn write(pairs: &[(day13::El, day13::El)]) -> Vec<String> {
+    pairs.iter().map(|(l, r)| format!("{l}\n{r}\n")).collect()
+}
+
+fuzz_target!(|input: &str| {
+    if let Ok(pairs) = day13::parse(input) {
+        // Packets compare equal to their nested versions, so compare them written out instead.
+        let pairs_text = write(&pairs);
+        assert_eq!(write(&day13::parse(&pairs_text.join("\n")).unwrap()), pairs_text);
+    }
+});
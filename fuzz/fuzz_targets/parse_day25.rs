@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(fuel) = day25::parse(input) {
+        for n in fuel.into_iter().filter(|&n| n > 0) {
+            assert_eq!(day25::parse(&day25::encode(n)).unwrap(), [n]);
+        }
+    }
+});
@@ -1,2 +1,2 @@
 #!/usr/bin/env sh
-cargo run -r -p "$1" < "$1/input.txt"
+cargo run -r -p aoc -- run "$1"
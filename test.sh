@@ -1,2 +1,2 @@
 #!/usr/bin/env sh
-cargo run -p "$1" < "$1/test.txt"
+cargo run -p aoc -- run "$1" --input test